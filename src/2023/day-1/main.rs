@@ -0,0 +1,219 @@
+use std::fmt;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 1;
+
+// Part 1
+static DIGITS_P1: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+// Part 2
+static DIGITS_P2: [&str; 20] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", //
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn main() {
+    println!("Hello Day 1!\n");
+
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+    let document = match parse(&input) {
+        Ok(document) => document,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+    match part_one(document) {
+        Ok(coordinates_p1) => {
+            println!("What is the sum of all of the calibration values?\n{coordinates_p1}\n")
+        }
+        Err(err) => println!("{}\n", err),
+    }
+
+    match part_two(document) {
+        Ok(coordinates_p2) => println!(
+            "What is the sum of all of the corrected calibration values?\n{coordinates_p2}"
+        ),
+        Err(err) => println!("{}", err),
+    }
+}
+
+// Each line is read differently by each part, so parsing only checks every line can be read by part 2
+pub(crate) fn parse(input: &str) -> Result<&str, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    verbose!("{} lines", input.lines().count());
+    match input
+        .lines()
+        .position(|line| !DIGITS_P2.iter().any(|digit| line.contains(digit)))
+    {
+        Some(index) => Err(ParseError::NoDigits { line: index + 1 }),
+        None => Ok(input),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    NoDigits { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NoDigits { line } => {
+                write!(f, "Line {} has no digits to calibrate with", line)
+            }
+        }
+    }
+}
+
+pub(crate) fn part_one(document: &str) -> Result<u32, ParseError> {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    parse_calibration(document, &DIGITS_P1)
+}
+
+pub(crate) fn part_two(document: &str) -> Result<u32, ParseError> {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    parse_calibration(document, &DIGITS_P2)
+}
+
+// Part 1 only reads numeric digits, so a line parse accepted may still have none for it
+fn parse_calibration(document: &str, digits: &[&str]) -> Result<u32, ParseError> {
+    return document
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            parse_calibration_line(line, digits).ok_or(ParseError::NoDigits { line: index + 1 })
+        })
+        .sum();
+}
+
+#[derive(Debug)]
+struct Match<'a> {
+    pattern: &'a str,
+    index: Option<usize>,
+}
+
+fn parse_calibration_line(text: &str, digits: &[&str]) -> Option<u32> {
+    let first = digits
+        .iter()
+        .map(|pattern| Match {
+            pattern: pattern,
+            index: text.find(pattern),
+        })
+        .filter(|matched| matched.index.is_some())
+        .reduce(|cur, next| if next.index < cur.index { next } else { cur })?;
+    let last = digits
+        .iter()
+        .map(|pattern| Match {
+            pattern: pattern,
+            index: text.rfind(pattern),
+        })
+        .filter(|matched| matched.index.is_some())
+        .reduce(|cur, next| if next.index > cur.index { next } else { cur })?;
+
+    let value = 10 * to_digit(first.pattern) + to_digit(last.pattern);
+    trace!(
+        "{:?} reads {} from {} and {}",
+        text,
+        value,
+        first.pattern,
+        last.pattern
+    );
+    return Some(value);
+}
+
+fn to_digit(num: &str) -> u32 {
+    match num {
+        "0" | "zero" => 0,
+        "1" | "one" => 1,
+        "2" | "two" => 2,
+        "3" | "three" => 3,
+        "4" | "four" => 4,
+        "5" | "five" => 5,
+        "6" | "six" => 6,
+        "7" | "seven" => 7,
+        "8" | "eight" => 8,
+        "9" | "nine" => 9,
+        _ => panic!("Oops!"),
+    }
+}
+
+#[test]
+fn test_parse_calibration_line() {
+    assert_eq!(parse_calibration_line("1abc2", &DIGITS_P1), Some(12));
+}
+
+#[test]
+fn test_parse_calibration() {
+    assert_eq!(
+        parse_calibration(
+            "1abc2
+            pqr3stu8vwx
+            a1b2c3d4e5f
+            treb7uchet",
+            &DIGITS_P1
+        ),
+        Ok(142)
+    );
+}
+
+// Part 2
+#[test]
+fn test_find_first_digit_letter() {
+    assert_eq!(parse_calibration_line("two1nine", &DIGITS_P2), Some(29));
+}
+
+#[test]
+fn test_parse_calibration_letter() {
+    assert_eq!(
+        parse_calibration(
+            "two1nine
+            eightwothree
+            abcone2threexyz
+            xtwone3four
+            4nineeightseven2
+            zoneight234
+            7pqrstsixteen",
+            &DIGITS_P2
+        ),
+        Ok(281)
+    );
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        parse("two1nine\nabc"),
+        Err(ParseError::NoDigits { line: 2 })
+    );
+    assert_eq!(parse("eightwothree"), Ok("eightwothree"));
+}
+
+#[test]
+fn test_parts_without_digits() {
+    // Part 2's example has lines only spelled out digits, which part 1 can't calibrate
+    assert_eq!(part_two("1abc2\neightwothree"), Ok(95));
+    assert_eq!(
+        part_one("1abc2\neightwothree"),
+        Err(ParseError::NoDigits { line: 2 })
+    );
+    assert_eq!(
+        part_two("two1nine\n\nabc"),
+        Err(ParseError::NoDigits { line: 2 })
+    );
+    assert_eq!(
+        ParseError::NoDigits { line: 2 }.to_string(),
+        "Line 2 has no digits to calibrate with"
+    );
+}
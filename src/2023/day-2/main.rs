@@ -0,0 +1,246 @@
+use std::cmp::max;
+use std::fmt;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 2;
+
+fn main() {
+    println!("Hello Day 2!\n");
+
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let games = match parse(&input) {
+        Ok(games) => games,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let possible = part_one(&games);
+    println!("Sum of possible game id's with 12 red cubes, 13 green cubes, and 14 blue cubes: {possible}\n");
+
+    let power_sum = part_two(&games);
+    println!("Sum of power of all games is: {power_sum}");
+}
+
+pub(crate) fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    let games: Vec<Game> = input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| parse_game(text, index + 1))
+        .collect::<Result<_, _>>()?;
+    verbose!("{} games", games.len());
+    Ok(games)
+}
+
+pub(crate) fn part_one(games: &[Game]) -> u16 {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    count_possible_games(games, 12, 13, 14)
+}
+
+pub(crate) fn part_two(games: &[Game]) -> u64 {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    min_needed(games)
+}
+
+#[derive(Debug)]
+pub(crate) struct Game {
+    id: u8,
+    max_round: Round,
+}
+
+#[derive(Debug)]
+struct Round {
+    red: u8,
+    green: u8,
+    blue: u8,
+}
+
+fn count_possible_games(games: &[Game], red: u8, green: u8, blue: u8) -> u16 {
+    games
+        .iter()
+        .filter(|game| {
+            let possible = game.max_round.red <= red
+                && game.max_round.green <= green
+                && game.max_round.blue <= blue;
+            trace!("Game {} possible: {}", game.id, possible);
+            possible
+        })
+        .fold(0, |acc, next: &Game| acc + (next.id as u16))
+}
+
+fn min_needed(games: &[Game]) -> u64 {
+    games.iter().fold(0, |acc, next: &Game| {
+        acc + (next.max_round.red as u64)
+            * (next.max_round.blue as u64)
+            * (next.max_round.green as u64)
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    MissingRounds { line: usize },
+    InvalidId { line: usize },
+    InvalidCount { line: usize },
+    UnknownColor { line: usize, color: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingRounds { line } => write!(f, "Line {} has no rounds", line),
+            ParseError::InvalidId { line } => {
+                write!(
+                    f,
+                    "Line {} should start with Game and an id up to 255",
+                    line
+                )
+            }
+            ParseError::InvalidCount { line } => {
+                write!(
+                    f,
+                    "Line {} has a cube count which is not a number up to 255",
+                    line
+                )
+            }
+            ParseError::UnknownColor { line, color } => {
+                write!(f, "Line {} has cubes of unknown color '{}'", line, color)
+            }
+        }
+    }
+}
+
+fn parse_game(text: &str, line: usize) -> Result<Game, ParseError> {
+    let (name, rounds) = text
+        .trim()
+        .split_once(':')
+        .ok_or(ParseError::MissingRounds { line })?;
+    let id = name
+        .strip_prefix("Game ")
+        .and_then(|id| id.parse::<u8>().ok())
+        .ok_or(ParseError::InvalidId { line })?;
+    let empty = Round {
+        red: 0,
+        green: 0,
+        blue: 0,
+    };
+    let max_round = rounds.split(";").try_fold(empty, |acc, round| {
+        let next = parse_round(round, line)?;
+        Ok(Round {
+            red: max(acc.red, next.red),
+            green: max(acc.green, next.green),
+            blue: max(acc.blue, next.blue),
+        })
+    })?;
+    trace!("Game {} needs at least {:?}", id, max_round);
+    Ok(Game {
+        id: id,
+        max_round: max_round,
+    })
+}
+
+fn parse_round(text: &str, line: usize) -> Result<Round, ParseError> {
+    let mut red: u8 = 0;
+    let mut green: u8 = 0;
+    let mut blue: u8 = 0;
+    for pick in text.split(',') {
+        let mut pick_iter = pick.trim().split(" ");
+        let value: u8 = pick_iter
+            .next()
+            .unwrap()
+            .parse()
+            .map_err(|_| ParseError::InvalidCount { line })?;
+        let color = pick_iter.next().unwrap_or("");
+
+        match color {
+            "red" => red = value,
+            "green" => green = value,
+            "blue" => blue = value,
+            _ => {
+                return Err(ParseError::UnknownColor {
+                    line,
+                    color: color.to_string(),
+                })
+            }
+        }
+    }
+    Ok(Round {
+        red: red,
+        green: green,
+        blue: blue,
+    })
+}
+
+#[test]
+fn test_count_possible_games() {
+    assert_eq!(
+        count_possible_games(
+            &parse(
+                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
+            )
+            .unwrap(),
+            12, // Red
+            13, // Blue
+            14  // Green
+        ),
+        8
+    );
+}
+
+#[test]
+fn test_min_needed() {
+    assert_eq!(
+        min_needed(
+            &parse(
+                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+            )
+            .unwrap()
+        ),
+        2286
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("Game 1: 3 blue\nGame 2 3 blue").unwrap_err(),
+        ParseError::MissingRounds { line: 2 }
+    );
+    assert_eq!(
+        parse("Game 256: 3 blue").unwrap_err(),
+        ParseError::InvalidId { line: 1 }
+    );
+    assert_eq!(
+        parse("Game 1: 3 blue; 1000 red").unwrap_err(),
+        ParseError::InvalidCount { line: 1 }
+    );
+    assert_eq!(
+        parse("Game 1: 3 purple").unwrap_err(),
+        ParseError::UnknownColor {
+            line: 1,
+            color: "purple".to_string()
+        }
+    );
+}
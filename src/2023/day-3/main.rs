@@ -1,26 +1,68 @@
 use std::collections::HashMap;
-use std::fs;
+use std::fmt;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 3;
 
 static RADIX: u32 = 10;
 
 fn main() {
     println!("Hello Day 3!\n");
 
-    let input = fs::read_to_string("input.txt").expect("Should have been able to read the file");
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
 
-    let schematic = Schematic::parse(&input);
+    let schematic = match parse(&input) {
+        Ok(schematic) => schematic,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
 
-    let engine_part_sum = sum_engine_parts(&schematic);
+    let engine_part_sum = part_one(&schematic);
 
     println!("Sum of engine part numbers: {engine_part_sum}");
 
-    let gear_ratio_sum = sum_gear_ratios(&schematic);
+    let gear_ratio_sum = part_two(&schematic);
 
     println!("Sum of gear part numbers: {gear_ratio_sum}");
 }
 
+pub(crate) fn parse(input: &str) -> Result<Schematic, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    let schematic = Schematic::parse(input)?;
+    verbose!(
+        "{} rows, {} numbers next to a symbol",
+        schematic.rows.len(),
+        schematic.neighbors.len()
+    );
+    Ok(schematic)
+}
+
+pub(crate) fn part_one(schematic: &Schematic) -> u32 {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    sum_engine_parts(schematic)
+}
+
+pub(crate) fn part_two(schematic: &Schematic) -> u32 {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    sum_gear_ratios(schematic)
+}
+
 #[derive(Debug)]
-struct Schematic {
+pub(crate) struct Schematic {
     rows: Vec<Row>,
     neighbors: Vec<Neighbor>,
 }
@@ -53,7 +95,7 @@ struct Symbol {
 }
 
 impl Schematic {
-    fn parse(text: &str) -> Schematic {
+    fn parse(text: &str) -> Result<Schematic, ParseError> {
         let mut schematic = Schematic {
             rows: Vec::new(),
             neighbors: Vec::new(),
@@ -81,7 +123,14 @@ impl Schematic {
                     while let Some(true) = char_iter.peek().map(|(_, value)| value.is_digit(RADIX))
                     {
                         let (_, value) = char_iter.next().unwrap();
-                        number.value = number.value * 10 + value.to_digit(RADIX).unwrap();
+                        number.value = number
+                            .value
+                            .checked_mul(10)
+                            .and_then(|tens| tens.checked_add(value.to_digit(RADIX).unwrap()))
+                            .ok_or(ParseError::NumberTooLarge {
+                                line: row + 1,
+                                column: column + 1,
+                            })?;
                         number.size = number.size + 1;
                     }
                     schematic.rows[row].numbers.push(number);
@@ -130,15 +179,32 @@ impl Schematic {
                     )
                 }
 
-                schematic.neighbors.extend(
-                    symbols
-                        .into_iter()
-                        .map(|symbol| Neighbor { number, symbol }),
-                );
+                for symbol in symbols {
+                    trace!("{:?} neighbors {:?}", number, symbol);
+                    schematic.neighbors.push(Neighbor { number, symbol });
+                }
             }
         }
 
-        schematic
+        Ok(schematic)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    // Position of the first digit of the number
+    NumberTooLarge { line: usize, column: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NumberTooLarge { line, column } => write!(
+                f,
+                "Number at line {} column {} does not fit in a u32",
+                line, column
+            ),
+        }
     }
 }
 
@@ -183,6 +249,7 @@ fn sum_gear_ratios(schematic: &Schematic) -> u32 {
         .filter(|numbers| numbers.len() >= 2)
         // Calculate gear ratio from gears pairs found
         .map(|numbers| {
+            trace!("Gear with {:?}", numbers);
             numbers
                 .into_iter()
                 .map(|number| number.value)
@@ -194,8 +261,9 @@ fn sum_gear_ratios(schematic: &Schematic) -> u32 {
 #[test]
 fn test_sum_engine_parts() {
     assert_eq!(
-        sum_engine_parts(&Schematic::parse(
-            "\
+        sum_engine_parts(
+            &Schematic::parse(
+                "\
 467..114..
 ...*......
 ..35..633.
@@ -206,7 +274,9 @@ fn test_sum_engine_parts() {
 ......755.
 ...$.*....
 .664.598.."
-        )),
+            )
+            .unwrap()
+        ),
         4361
     )
 }
@@ -214,8 +284,9 @@ fn test_sum_engine_parts() {
 #[test]
 fn test_sum_gear_ratios() {
     assert_eq!(
-        sum_gear_ratios(&Schematic::parse(
-            "\
+        sum_gear_ratios(
+            &Schematic::parse(
+                "\
 467..114..
 ...*......
 ..35..633.
@@ -226,7 +297,17 @@ fn test_sum_gear_ratios() {
 ......755.
 ...$.*....
 .664.598.."
-        )),
+            )
+            .unwrap()
+        ),
         467835
     )
 }
+
+#[test]
+fn test_parse_number_too_large() {
+    assert_eq!(
+        Schematic::parse("..*\n.12345678901.").unwrap_err(),
+        ParseError::NumberTooLarge { line: 2, column: 2 }
+    )
+}
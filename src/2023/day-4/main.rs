@@ -1,25 +1,64 @@
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fmt;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 4;
 
 fn main() {
     println!("Hello Day 4!\n");
 
-    let input = fs::read_to_string("input.txt").expect("Should have been able to read the file");
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
 
-    let cards = input
-        .lines()
-        .map(SctratchCard::parse)
-        .collect::<Vec<SctratchCard>>();
+    let cards = match parse(&input) {
+        Ok(cards) => cards,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
 
-    let sum_scores = sum_scratchcards_points(&cards);
+    let sum_scores = part_one(&cards);
     println!("Scratch Cards score sum is: {sum_scores}");
 
-    let total_scratchcards = sum_won_scratchcards(&cards);
+    let total_scratchcards = part_two(&cards);
     println!("Winning Scratch Cards total points is: {total_scratchcards}");
 }
 
+pub(crate) fn parse(input: &str) -> Result<Vec<SctratchCard>, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    let cards = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| SctratchCard::parse(line, index + 1))
+        .collect::<Result<Vec<SctratchCard>, ParseError>>()?;
+    verbose!("{} cards", cards.len());
+    Ok(cards)
+}
+
+pub(crate) fn part_one(cards: &[SctratchCard]) -> u32 {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    sum_scratchcards_points(cards)
+}
+
+pub(crate) fn part_two(cards: &[SctratchCard]) -> u32 {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    sum_won_scratchcards(cards)
+}
+
 #[derive(Debug)]
-struct SctratchCard {
+pub(crate) struct SctratchCard {
     id: u32,
     // Array sizes derived from input.txt structure
     winning: HashSet<u32>,
@@ -27,44 +66,44 @@ struct SctratchCard {
 }
 
 impl SctratchCard {
-    fn parse(line: &str) -> SctratchCard {
+    fn parse(line: &str, number: usize) -> Result<SctratchCard, ParseError> {
         // Expect line to be in the following format
         // Card <num>: <num> ... | <num> ...
         let mut parts = line.split(":");
         let id = if let Some(card_id) = parts.next() {
             card_id
                 .split(" ")
-                .find(|id| id.parse::<u32>().is_ok())
-                .expect("Card id not found")
-                .parse::<u32>()
-                .expect("Card id not a number")
+                .find_map(|id| id.parse::<u32>().ok())
+                .ok_or(ParseError::MissingId { line: number })?
         } else {
-            // We could return Result<>, but this is easy
-            dbg!(line);
-            panic!("Could not parse line");
+            trace!("No card id in {:?}", line);
+            return Err(ParseError::MissingId { line: number });
         };
 
-        let mut numbers = parts.next().expect("Numbers not found").split(" | ");
+        let mut numbers = parts
+            .next()
+            .ok_or(ParseError::MissingNumbers { line: number })?
+            .split(" | ");
 
         let winning = numbers
             .next()
-            .expect("Winning numbers not found")
+            .ok_or(ParseError::MissingNumbers { line: number })?
             .split(" ")
             .filter_map(|num| num.parse::<u32>().ok())
             .collect::<HashSet<u32>>();
 
         let provided = numbers
             .next()
-            .expect("Provided numbers not found")
+            .ok_or(ParseError::NoSeparator { line: number })?
             .split(" ")
             .filter_map(|num| num.parse::<u32>().ok())
             .collect::<HashSet<u32>>();
 
-        SctratchCard {
+        Ok(SctratchCard {
             id,
             winning,
             provided,
-        }
+        })
     }
 
     fn winning_numbers(&self) -> HashSet<u32> {
@@ -87,7 +126,14 @@ impl SctratchCard {
 }
 
 fn sum_scratchcards_points(cards: &[SctratchCard]) -> u32 {
-    cards.iter().map(|card| card.score()).sum()
+    cards
+        .iter()
+        .map(|card| {
+            let score = card.score();
+            trace!("Card {} scores {}", card.id, score);
+            score
+        })
+        .sum()
 }
 
 fn sum_won_scratchcards(cards: &[SctratchCard]) -> u32 {
@@ -99,6 +145,7 @@ fn sum_won_scratchcards(cards: &[SctratchCard]) -> u32 {
         .fold(HashMap::<u32, u32>::new(), |mut acc, card| {
             let self_copies = acc.get(&card.id).unwrap_or(&0) + 1;
             acc.insert(card.id, self_copies);
+            trace!("Card {} has {} copies", card.id, self_copies);
 
             // Add coppies from winning numbers
             let card_ids = card
@@ -118,6 +165,28 @@ fn sum_won_scratchcards(cards: &[SctratchCard]) -> u32 {
         .sum()
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    MissingId { line: usize },
+    MissingNumbers { line: usize },
+    // Winning and provided numbers are split by ` | `
+    NoSeparator { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingId { line } => write!(f, "Line {} has no card id", line),
+            ParseError::MissingNumbers { line } => {
+                write!(f, "Line {} has no numbers after the card id", line)
+            }
+            ParseError::NoSeparator { line } => {
+                write!(f, "Line {} has no ` | ` before the provided numbers", line)
+            }
+        }
+    }
+}
+
 #[test]
 fn test_sum_scratchcards_points() {
     let cards = "\
@@ -128,7 +197,8 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
         .lines()
-        .map(SctratchCard::parse)
+        .enumerate()
+        .map(|(index, line)| SctratchCard::parse(line, index + 1).unwrap())
         .collect::<Vec<SctratchCard>>();
 
     assert_eq!(sum_scratchcards_points(&cards), 13)
@@ -144,8 +214,25 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
         .lines()
-        .map(SctratchCard::parse)
+        .enumerate()
+        .map(|(index, line)| SctratchCard::parse(line, index + 1).unwrap())
         .collect::<Vec<SctratchCard>>();
 
     assert_eq!(sum_won_scratchcards(&cards), 30)
 }
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("Card: 1 2 | 3").unwrap_err(),
+        ParseError::MissingId { line: 1 }
+    );
+    assert_eq!(
+        parse("Card 1: 1 2 | 3\nCard 2").unwrap_err(),
+        ParseError::MissingNumbers { line: 2 }
+    );
+    assert_eq!(
+        parse("Card 1: 1 2 3").unwrap_err(),
+        ParseError::NoSeparator { line: 1 }
+    );
+}
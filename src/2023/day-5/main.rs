@@ -0,0 +1,487 @@
+use std::env;
+use std::fmt;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+#[path = "../../parallel.rs"]
+mod parallel;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 5;
+
+fn main() {
+    println!("Hello Day 5!\n");
+
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let almanac = match parse(&input) {
+        Ok(almanac) => almanac,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let lowest_location = part_one(&almanac);
+    println!("Lowest seed location: {lowest_location}");
+
+    // Pass --brute-force to check every seed one at a time, which takes hours on a real input
+    let lowest_seed_range_location = if env::args().any(|arg| arg == "--brute-force") {
+        min_seed_range_loction(&almanac)
+    } else {
+        part_two(&almanac)
+    };
+    println!("Lowest seed range location: {lowest_seed_range_location}");
+}
+
+pub(crate) fn parse(input: &str) -> Result<Almanac, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    let almanac = Almanac::parse(input)?;
+    verbose!("{} seeds", almanac.seeds.len());
+    for (name, maps) in almanac.layers() {
+        verbose!("{} has {} maps", name, maps.len());
+    }
+    Ok(almanac)
+}
+
+pub(crate) fn part_one(almanac: &Almanac) -> u64 {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    min_seed_location(almanac)
+}
+
+pub(crate) fn part_two(almanac: &Almanac) -> u64 {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    min_seed_range_location_split(almanac)
+}
+
+// Inclusive start and end of a run of numbers, so a seed range of length 2 from 79 is (79, 80)
+type Span = (u64, u64);
+
+#[derive(Debug)]
+struct Map {
+    dest: u64,
+    source: u64,
+    range: u64,
+}
+
+impl Map {
+    // Moves the range numbers from source, so a map of length 2 from 98 moves 98 and 99
+    fn transform(&self, value: u64) -> Option<u64> {
+        if value >= self.source && value - self.source < self.range {
+            Some(value - self.source + self.dest)
+        } else {
+            None
+        }
+    }
+
+    // Splits the span into the part this map moves, and the parts left for the next map
+    fn transform_span(&self, (start, end): Span) -> (Option<Span>, Vec<Span>) {
+        if self.range == 0 {
+            return (None, vec![(start, end)]);
+        }
+        let last = self.source + self.range - 1;
+        if end < self.source || start > last {
+            return (None, vec![(start, end)]);
+        }
+
+        let mut rest = Vec::new();
+        if start < self.source {
+            rest.push((start, self.source - 1));
+        }
+        if end > last {
+            rest.push((last + 1, end));
+        }
+        let moved = (
+            start.max(self.source) - self.source + self.dest,
+            end.min(last) - self.source + self.dest,
+        );
+        (Some(moved), rest)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Almanac {
+    seeds: Vec<u64>,
+    seed_soil: Vec<Map>,
+    soil_fertilizer: Vec<Map>,
+    fertilizer_water: Vec<Map>,
+    water_light: Vec<Map>,
+    light_temprature: Vec<Map>,
+    temperature_humidity: Vec<Map>,
+    humidity_location: Vec<Map>,
+}
+
+impl Almanac {
+    fn parse(text: &str) -> Result<Almanac, ParseError> {
+        // Does not support /r/n line format
+        let mut lines = text.trim().split("\n\n");
+
+        // We assume the input file is in a specific order
+        let seeds = Self::parse_seeds(lines.next().unwrap())?;
+        let mut map = |name| {
+            lines
+                .next()
+                .ok_or(ParseError::MissingMap(name))
+                .and_then(|value| Self::parse_map(name, value))
+        };
+        Ok(Almanac {
+            seeds,
+            seed_soil: map("seed-to-soil")?,
+            soil_fertilizer: map("soil-to-fertilizer")?,
+            fertilizer_water: map("fertilizer-to-water")?,
+            water_light: map("water-to-light")?,
+            light_temprature: map("light-to-temperature")?,
+            temperature_humidity: map("temperature-to-humidity")?,
+            humidity_location: map("humidity-to-location")?,
+        })
+    }
+
+    fn parse_seeds(value: &str) -> Result<Vec<u64>, ParseError> {
+        let seeds: Vec<u64> = value
+            .split(':')
+            .nth(1)
+            .ok_or(ParseError::MissingSeeds)?
+            .split(" ")
+            .filter_map(|digit| digit.parse::<u64>().ok())
+            .collect();
+
+        // Part two reads the seeds as pairs of a start and a length
+        if seeds.is_empty() {
+            return Err(ParseError::NoSeeds);
+        }
+        if !seeds.len().is_multiple_of(2) {
+            return Err(ParseError::UnpairedSeed(seeds[seeds.len() - 1]));
+        }
+        if let Some(pair) = seeds
+            .chunks(2)
+            .find(|pair| pair[1] == 0 || pair[0].checked_add(pair[1] - 1).is_none())
+        {
+            return Err(ParseError::InvalidSeedRange {
+                start: pair[0],
+                length: pair[1],
+            });
+        }
+        Ok(seeds)
+    }
+
+    fn parse_map(name: &'static str, value: &str) -> Result<Vec<Map>, ParseError> {
+        value
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let digits = line
+                    .split(" ")
+                    .filter_map(|digit| digit.parse::<u64>().ok())
+                    .collect::<Vec<u64>>();
+                let invalid = || ParseError::InvalidMapLine {
+                    map: name,
+                    line: line.to_string(),
+                };
+                if digits.len() < 3 {
+                    return Err(invalid());
+                }
+                // Both ends of every range must be a u64 for transform
+                if digits[0].checked_add(digits[2]).is_none()
+                    || digits[1].checked_add(digits[2]).is_none()
+                {
+                    return Err(invalid());
+                }
+                Ok(Map {
+                    dest: digits[0],
+                    source: digits[1],
+                    range: digits[2],
+                })
+            })
+            .collect::<Result<Vec<Map>, ParseError>>()
+    }
+
+    // Named the way the input names them
+    fn layers(&self) -> Vec<(&'static str, &Vec<Map>)> {
+        vec![
+            ("seed-to-soil", &self.seed_soil),
+            ("soil-to-fertilizer", &self.soil_fertilizer),
+            ("fertilizer-to-water", &self.fertilizer_water),
+            ("water-to-light", &self.water_light),
+            ("light-to-temperature", &self.light_temprature),
+            ("temperature-to-humidity", &self.temperature_humidity),
+            ("humidity-to-location", &self.humidity_location),
+        ]
+    }
+
+    fn get_location(&self, seed: u64) -> u64 {
+        self.layers().iter().fold(seed, |acc, (name, maps)| {
+            let next = maps
+                .iter()
+                .filter_map(|mapper| mapper.transform(acc))
+                .next()
+                .unwrap_or(acc);
+            trace!("{} moves {} to {}", name, acc, next);
+            next
+        })
+    }
+
+    // Like get_location for whole spans of seeds, the first map to cover a number moves it
+    fn get_location_spans(&self, seeds: Vec<Span>) -> Vec<Span> {
+        self.layers().iter().fold(seeds, |spans, (name, maps)| {
+            let mut moved = Vec::new();
+            let mut pending = spans;
+            for map in maps.iter() {
+                let mut rest = Vec::new();
+                for span in pending {
+                    let (span_moved, span_rest) = map.transform_span(span);
+                    moved.extend(span_moved);
+                    rest.extend(span_rest);
+                }
+                pending = rest;
+            }
+            moved.extend(pending);
+            verbose!("{} leaves {} spans", name, moved.len());
+            trace!("{:?}", moved);
+            moved
+        })
+    }
+}
+
+fn min_seed_location(almanac: &Almanac) -> u64 {
+    almanac
+        .seeds
+        .iter()
+        .map(|seed| almanac.get_location(*seed))
+        .min()
+        .unwrap()
+}
+
+fn min_seed_range_location_split(almanac: &Almanac) -> u64 {
+    let seeds = almanac
+        .seeds
+        .chunks(2)
+        .map(|window| (window[0], window[0] + window[1] - 1))
+        .collect();
+    almanac
+        .get_location_spans(seeds)
+        .iter()
+        .map(|span| span.0)
+        .min()
+        .unwrap()
+}
+
+// Each seed range is cut into this many pieces, so a few long ranges still keep every core busy
+const PIECES: u64 = 16;
+
+// The inclusive range from start to end cut into at most the given number of inclusive pieces
+fn split(start: u64, end: u64, pieces: u64) -> Vec<(u64, u64)> {
+    let size = (end - start) / pieces + 1;
+    (0..pieces)
+        .map(|piece| start.saturating_add(piece.saturating_mul(size)))
+        .take_while(|first| *first <= end)
+        .map(|first| (first, first.saturating_add(size - 1).min(end)))
+        .collect()
+}
+
+pub(crate) fn min_seed_range_loction(almanac: &Almanac) -> u64 {
+    // This is way too computationally expensive, and takes multiple hours to run
+    // Kept as the reference for min_seed_range_location_split
+    let pieces: Vec<(u64, u64)> = almanac
+        .seeds
+        .chunks(2)
+        .flat_map(|window| split(window[0], window[0] + window[1] - 1, PIECES))
+        .collect();
+    parallel::map(&pieces, |(start, end)| {
+        (*start..=*end)
+            .map(|seed| (seed, almanac.get_location(seed)))
+            .map(|(seed, location)| {
+                trace!("{} : {}", seed, location);
+                location
+            })
+            .min()
+            .unwrap()
+    })
+    .into_iter()
+    .min()
+    .unwrap()
+    // 0
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    MissingSeeds,
+    NoSeeds,
+    // The last seed, which has no length to make it a range
+    UnpairedSeed(u64),
+    // A range needs at least one seed, and every seed in it must fit in a u64
+    InvalidSeedRange { start: u64, length: u64 },
+    MissingMap(&'static str),
+    // Each map line needs a destination, source and length which fit in a u64
+    InvalidMapLine { map: &'static str, line: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingSeeds => write!(f, "Almanac should start with seeds:"),
+            ParseError::NoSeeds => write!(f, "Almanac has no seeds"),
+            ParseError::UnpairedSeed(seed) => {
+                write!(f, "Seed {} has no length to make it a range", seed)
+            }
+            ParseError::InvalidSeedRange { start, length } => write!(
+                f,
+                "Seed range from {} of length {} is empty or goes past the largest u64",
+                start, length
+            ),
+            ParseError::MissingMap(name) => write!(f, "Almanac has no {} map", name),
+            ParseError::InvalidMapLine { map, line } => {
+                write!(f, "Line '{}' of the {} map is not a valid range", line, map)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static ALMANAC_TEXT: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_part_one() {
+        let almanac = Almanac::parse(ALMANAC_TEXT).unwrap();
+        assert_eq!(min_seed_location(&almanac), 35);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let almanac = Almanac::parse(ALMANAC_TEXT).unwrap();
+        assert_eq!(min_seed_range_loction(&almanac), 46);
+        assert_eq!(min_seed_range_location_split(&almanac), 46);
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(
+            split(79, 93, 4),
+            vec![(79, 82), (83, 86), (87, 90), (91, 93)]
+        );
+        assert_eq!(split(5, 6, 16), vec![(5, 5), (6, 6)]);
+        assert_eq!(split(7, 7, 16), vec![(7, 7)]);
+        assert_eq!(
+            split(u64::MAX - 2, u64::MAX, 2),
+            vec![(u64::MAX - 2, u64::MAX - 1), (u64::MAX, u64::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_range_ends() {
+        let map = Map {
+            dest: 50,
+            source: 98,
+            range: 2,
+        };
+        assert_eq!(map.transform(97), None);
+        assert_eq!(map.transform(99), Some(51));
+        assert_eq!(map.transform(100), None);
+        assert_eq!(
+            map.transform_span((97, 100)),
+            (Some((50, 51)), vec![(97, 97), (100, 100)])
+        );
+        let almanac = Almanac::parse(&ALMANAC_TEXT.replace("79 14 55 13", "100 1")).unwrap();
+        assert_eq!(almanac.seed_soil[0].transform(100), None);
+        assert_eq!(
+            almanac.get_location_spans(vec![(100, 100)]),
+            vec![(almanac.get_location(100), almanac.get_location(100))]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Almanac::parse("79 14").unwrap_err(),
+            ParseError::MissingSeeds
+        );
+        assert_eq!(
+            Almanac::parse(&ALMANAC_TEXT.replace("seeds: 79 14 55 13", "seeds:")).unwrap_err(),
+            ParseError::NoSeeds
+        );
+        assert_eq!(
+            Almanac::parse(&ALMANAC_TEXT.replace("seeds: 79 14 55 13", "seeds: 79 14 55"))
+                .unwrap_err(),
+            ParseError::UnpairedSeed(55)
+        );
+        assert_eq!(
+            Almanac::parse(&ALMANAC_TEXT.replace("79 14", "18446744073709551600 17")).unwrap_err(),
+            ParseError::InvalidSeedRange {
+                start: 18446744073709551600,
+                length: 17
+            }
+        );
+        assert_eq!(
+            Almanac::parse(&ALMANAC_TEXT.replace("79 14", "79 0")).unwrap_err(),
+            ParseError::InvalidSeedRange {
+                start: 79,
+                length: 0
+            }
+        );
+        // The last seed of the range is the largest u64
+        assert!(Almanac::parse(&ALMANAC_TEXT.replace("79 14", "18446744073709551600 16")).is_ok());
+        assert_eq!(
+            Almanac::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2").unwrap_err(),
+            ParseError::MissingMap("soil-to-fertilizer")
+        );
+        assert_eq!(
+            Almanac::parse(&ALMANAC_TEXT.replace("50 98 2", "50 98")).unwrap_err(),
+            ParseError::InvalidMapLine {
+                map: "seed-to-soil",
+                line: "50 98".to_string()
+            }
+        );
+        assert_eq!(
+            Almanac::parse(&ALMANAC_TEXT.replace("50 98 2", "50 18446744073709551615 2"))
+                .unwrap_err(),
+            ParseError::InvalidMapLine {
+                map: "seed-to-soil",
+                line: "50 18446744073709551615 2".to_string()
+            }
+        );
+    }
+}
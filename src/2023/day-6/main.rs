@@ -0,0 +1,395 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 6;
+
+fn main() {
+    println!("Hello Day 6!\n");
+
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let races = match parse(&input) {
+        Ok(races) => races,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let product_winning_presses_counts = part_one(&races);
+    println!("Product of number of ways each race can be won: {product_winning_presses_counts}");
+
+    let winning_values = part_two(&races);
+    println!("Number of ways to win race: {winning_values}");
+
+    for race in races.kerned {
+        if let Some(interval) = race.winning_interval() {
+            println!(
+                "Winning hold times: {}..={}, best hold time: {} beating the record by {}",
+                interval.start(),
+                interval.end(),
+                race.optimal_hold_time(),
+                race.record_margin().unwrap_or(0),
+            );
+        }
+    }
+}
+
+pub(crate) fn parse(input: &str) -> Result<Races, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    let races = Races {
+        separate: Race::parse(input, ParseMode::Separate)?,
+        kerned: Race::parse(input, ParseMode::Kerned)?,
+    };
+    verbose!(
+        "{} separate races, {} kerned",
+        races.separate.len(),
+        races.kerned.len()
+    );
+    Ok(races)
+}
+
+pub(crate) fn part_one(races: &Races) -> u64 {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    margin_of_error(&races.separate)
+}
+
+pub(crate) fn part_two(races: &Races) -> u64 {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    margin_of_error(&races.kerned)
+}
+
+#[cfg(test)]
+pub(crate) fn part_one_brute_force(races: &Races) -> u64 {
+    margin_of_error_brute_force(&races.separate)
+}
+
+#[cfg(test)]
+pub(crate) fn part_two_brute_force(races: &Races) -> u64 {
+    margin_of_error_brute_force(&races.kerned)
+}
+
+// The same sheet read both ways
+#[derive(Debug)]
+pub(crate) struct Races {
+    separate: Vec<Race>,
+    kerned: Vec<Race>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ParseMode {
+    // Every column of numbers is its own race (Part 1)
+    Separate,
+    // Spaces between digits are bad kerning, each line is one number (Part 2)
+    Kerned,
+}
+
+#[derive(Debug)]
+struct Race {
+    time: u64,
+    distance: u64,
+    // Speed gained for every millisecond the button is held
+    acceleration: u64,
+    // Milliseconds lost after releasing the button before the boat moves
+    penalty: u64,
+}
+
+impl Race {
+    fn parse(text: &str, mode: ParseMode) -> Result<Vec<Race>, ParseError> {
+        // Expect every line to be in the following format
+        // <Label>: <num> ...
+        let mut times = None;
+        let mut distances = None;
+        let mut accelerations = None;
+        let mut penalties = None;
+
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (label, values) = line
+                .split_once(':')
+                .ok_or(ParseError::MissingValues { line: index + 1 })?;
+            let values = Self::parse_values(values, mode)
+                .ok_or(ParseError::InvalidNumber { line: index + 1 })?;
+
+            match label.trim() {
+                "Time" => times = Some(values),
+                "Distance" => distances = Some(values),
+                "Acceleration" => accelerations = Some(values),
+                "Penalty" => penalties = Some(values),
+                label => return Err(ParseError::UnknownLabel(label.to_string())),
+            }
+        }
+
+        let times = times.ok_or(ParseError::MissingLine("Time"))?;
+        let distances = distances.ok_or(ParseError::MissingLine("Distance"))?;
+        let accelerations = accelerations.unwrap_or_else(|| vec![1; times.len()]);
+        let penalties = penalties.unwrap_or_else(|| vec![0; times.len()]);
+
+        if distances.len() != times.len()
+            || accelerations.len() != times.len()
+            || penalties.len() != times.len()
+        {
+            return Err(ParseError::MismatchedRaces);
+        }
+
+        (0..times.len())
+            .map(|i| {
+                // The furthest a boat could go is time * time * acceleration
+                let fits = times[i]
+                    .checked_mul(times[i])
+                    .and_then(|squared| squared.checked_mul(accelerations[i]))
+                    .and_then(|_| times[i].checked_add(penalties[i]))
+                    .is_some();
+                if !fits {
+                    return Err(ParseError::RaceTooLong { race: i + 1 });
+                }
+                let race = Race {
+                    time: times[i],
+                    distance: distances[i],
+                    acceleration: accelerations[i],
+                    penalty: penalties[i],
+                };
+                trace!("{:?} {:?}", mode, race);
+                Ok(race)
+            })
+            .collect()
+    }
+
+    fn parse_values(text: &str, mode: ParseMode) -> Option<Vec<u64>> {
+        match mode {
+            ParseMode::Separate => text
+                .split_whitespace()
+                .map(|digit| digit.parse::<u64>().ok())
+                .collect(),
+            ParseMode::Kerned => text
+                .split_whitespace()
+                .collect::<String>()
+                .parse::<u64>()
+                .ok()
+                .map(|value| vec![value]),
+        }
+    }
+
+    fn distance(&self, pressed_time: u64) -> u64 {
+        let moving_time = self.time.saturating_sub(pressed_time + self.penalty);
+        moving_time * pressed_time * self.acceleration
+    }
+
+    fn is_record(&self, pressed_time: u64) -> bool {
+        self.distance(pressed_time) > self.distance
+    }
+
+    fn optimal_hold_time(&self) -> u64 {
+        // distance is a parabola over pressed time, peaking half way through the moving time
+        self.time.saturating_sub(self.penalty) / 2
+    }
+
+    fn record_margin(&self) -> Option<u64> {
+        // How far past the record the best possible press travels
+        let best = self.distance(self.optimal_hold_time());
+        if best > self.distance {
+            Some(best - self.distance)
+        } else {
+            None
+        }
+    }
+
+    fn winning_interval(&self) -> Option<RangeInclusive<u64>> {
+        let optimal = self.optimal_hold_time();
+        if !self.is_record(optimal) {
+            return None;
+        }
+
+        // distance only increases up to the optimal time, and only decreases after
+        // so each edge of the interval can be binary searched
+        let (mut low, mut high) = (0, optimal);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.is_record(mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        let min = low;
+
+        let (mut low, mut high) = (optimal, self.time);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if self.is_record(mid) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        let max = low;
+
+        Some(min..=max)
+    }
+
+    fn record_pressed_times_count(&self) -> u64 {
+        self.winning_interval()
+            .map(|interval| interval.end() - interval.start() + 1)
+            .unwrap_or(0)
+    }
+
+    #[cfg(test)]
+    fn record_pressed_times_count_brute_force(&self) -> u64 {
+        // Reference for record_pressed_times_count, try every pressed time
+        (0..=self.time)
+            .filter(|pressed_time| self.is_record(*pressed_time))
+            .count() as u64
+    }
+}
+
+fn margin_of_error(races: &[Race]) -> u64 {
+    races
+        .iter()
+        .map(|race| {
+            trace!(
+                "Race of {}ms wins holding for {:?}",
+                race.time,
+                race.winning_interval()
+            );
+            race.record_pressed_times_count()
+        })
+        .product()
+}
+
+#[cfg(test)]
+fn margin_of_error_brute_force(races: &[Race]) -> u64 {
+    races
+        .iter()
+        .map(|race| race.record_pressed_times_count_brute_force())
+        .product()
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    MissingValues { line: usize },
+    InvalidNumber { line: usize },
+    UnknownLabel(String),
+    MissingLine(&'static str),
+    MismatchedRaces,
+    // Distances in the race would not fit in a u64
+    RaceTooLong { race: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingValues { line } => write!(f, "Line {} has no label", line),
+            ParseError::InvalidNumber { line } => {
+                write!(f, "Line {} has a value which is not a u64", line)
+            }
+            ParseError::UnknownLabel(label) => write!(f, "Unknown race line '{}'", label),
+            ParseError::MissingLine(label) => write!(f, "Races need a {} line", label),
+            ParseError::MismatchedRaces => {
+                write!(f, "Every line must describe the same number of races")
+            }
+            ParseError::RaceTooLong { race } => {
+                write!(f, "Race {} is too long to measure distances in", race)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEXT: &str = "\
+Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn test_part_one() {
+        let races = Race::parse(TEXT, ParseMode::Separate).unwrap();
+        assert_eq!(races.len(), 3);
+        assert_eq!(margin_of_error(&races), 288);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let races = Race::parse(TEXT, ParseMode::Kerned).unwrap();
+        assert_eq!(margin_of_error(&races), 71503);
+    }
+
+    #[test]
+    fn test_brute_force() {
+        let races = parse(TEXT).unwrap();
+        assert_eq!(part_one_brute_force(&races), 288);
+        assert_eq!(part_two_brute_force(&races), 71503);
+    }
+
+    #[test]
+    fn test_race_analysis() {
+        let races = Race::parse(TEXT, ParseMode::Separate).unwrap();
+
+        assert_eq!(races[0].winning_interval(), Some(2..=5));
+        assert_eq!(races[0].optimal_hold_time(), 3);
+        assert_eq!(races[0].record_margin(), Some(3));
+        assert_eq!(races[2].winning_interval(), Some(11..=19));
+    }
+
+    #[test]
+    fn test_extra_lines() {
+        let races = Race::parse(
+            "\
+Time:          7  15
+Distance:      9  40
+Acceleration:  2   1
+Penalty:       0   5",
+            ParseMode::Separate,
+        )
+        .unwrap();
+
+        // 7ms race at double speed, 15ms race that only moves for 10ms
+        assert_eq!(races[0].winning_interval(), Some(1..=6));
+        assert_eq!(races[1].winning_interval(), None);
+        assert_eq!(margin_of_error(&races), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("Time: 7\nDistance 9").unwrap_err(),
+            ParseError::MissingValues { line: 2 }
+        );
+        assert_eq!(
+            parse("Time: 7\nDistance: x").unwrap_err(),
+            ParseError::InvalidNumber { line: 2 }
+        );
+        assert_eq!(
+            parse("Time: 7\nSpeed: 9").unwrap_err(),
+            ParseError::UnknownLabel("Speed".to_string())
+        );
+        assert_eq!(
+            parse("Time: 7 15").unwrap_err(),
+            ParseError::MissingLine("Distance")
+        );
+        assert_eq!(
+            parse("Time: 7 15\nDistance: 9").unwrap_err(),
+            ParseError::MismatchedRaces
+        );
+        assert_eq!(
+            parse("Time: 7 5000000000\nDistance: 9 40").unwrap_err(),
+            ParseError::RaceTooLong { race: 2 }
+        );
+    }
+}
@@ -0,0 +1,612 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 7;
+
+fn main() {
+    println!("Hello Day 7!\n");
+
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let games = match parse(&input) {
+        Ok(games) => games,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let total_winnings = part_one(&games);
+    println!("Total winnings of hands: {total_winnings}");
+
+    let total_winnings = part_two(&games);
+    println!("Total winnings of hands with jokers: {total_winnings}");
+
+    // Pass --explain or --csv to see how every hand was ranked
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--explain") {
+        print!("\n{}", ranking_table(&ranking(&games.jokers)));
+    } else if args.iter().any(|arg| arg == "--csv") {
+        print!("\n{}", ranking_csv(&ranking(&games.jokers)));
+    }
+}
+
+pub(crate) fn parse(input: &str) -> Result<Games, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    let jacks = Deck::jacks();
+    let jokers = Deck::jokers();
+    let games = Games {
+        jacks: input
+            .lines()
+            .map(|line| Hand::parse(line, &jacks))
+            .collect::<Result<Vec<Hand>, ParseError>>()?,
+        jokers: input
+            .lines()
+            .map(|line| Hand::parse(line, &jokers))
+            .collect::<Result<Vec<Hand>, ParseError>>()?,
+    };
+    verbose!("{} hands", games.jacks.len());
+    Ok(games)
+}
+
+pub(crate) fn part_one(games: &Games) -> u32 {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    winnings(&games.jacks)
+}
+
+pub(crate) fn part_two(games: &Games) -> u32 {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    winnings(&games.jokers)
+}
+
+#[cfg(test)]
+pub(crate) fn part_two_brute_force(games: &Games) -> u32 {
+    // Rank every hand by trying each substitute for its jokers
+    let deck = Deck::jokers();
+    let hands: Vec<Hand> = games
+        .jokers
+        .iter()
+        .map(|hand| Hand {
+            rank: Hand::rank_brute_force(&hand.cards, &deck),
+            cards: hand.cards.clone(),
+            bid: hand.bid,
+            jokers_as: hand.jokers_as.clone(),
+        })
+        .collect();
+    winnings(&hands)
+}
+
+// The same hands dealt from each deck
+#[derive(Debug)]
+pub(crate) struct Games {
+    jacks: Vec<Hand>,
+    jokers: Vec<Hand>,
+}
+
+#[derive(Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
+struct Card {
+    // Sort by value, the symbol is only kept for display
+    value: u32,
+    symbol: char,
+}
+
+#[derive(Debug)]
+struct Deck {
+    // Ordered from weakest to strongest
+    cards: Vec<Card>,
+    // Card which may stand in for any other card when ranking a hand
+    joker: Option<Card>,
+}
+
+// The count of each distinct card in a hand, from largest to smallest group
+// Comparing signatures in order ranks hands of any size: [5] > [4, 1] > [3, 2] > [3, 1, 1] ...
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Rank(Vec<u32>);
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Hand {
+    // Sort by rank, then by card (high value)
+    rank: Rank,
+    cards: Vec<Card>,
+    bid: u32,
+    // The card every joker pretended to be, if the hand held any
+    jokers_as: Option<Card>,
+}
+
+#[derive(Debug)]
+struct RankedHand<'a> {
+    // 1 is the weakest hand
+    position: u32,
+    hand: &'a Hand,
+    // Index of the card which beat the next weaker hand of the same rank
+    tiebreak: Option<usize>,
+    contribution: u32,
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Card({})", self.letter())
+    }
+}
+
+impl fmt::Debug for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_slice() {
+            [5] => write!(f, "Five"),
+            [4, 1] => write!(f, "Four"),
+            [3, 2] => write!(f, "FullHouse"),
+            [3, 1, 1] => write!(f, "Three"),
+            [2, 2, 1] => write!(f, "TwoPair"),
+            [2, 1, 1, 1] => write!(f, "OnePair"),
+            [1, 1, 1, 1, 1] => write!(f, "HighCard"),
+            // Hands which are not five cards are named by their signature, such as 3+3
+            signature => {
+                let counts: Vec<String> = signature.iter().map(|count| count.to_string()).collect();
+                write!(f, "{}", counts.join("+"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for card in self.cards.iter() {
+            write!(f, "{}", card.letter())?;
+        }
+        Ok(())
+    }
+}
+
+impl Card {
+    fn letter(&self) -> char {
+        self.symbol
+    }
+}
+
+impl Deck {
+    fn new(symbols: &str, joker: Option<char>) -> Deck {
+        // Symbols are given from weakest to strongest
+        let cards: Vec<Card> = symbols
+            .chars()
+            .enumerate()
+            .map(|(index, symbol)| Card {
+                value: index as u32 + 1,
+                symbol,
+            })
+            .collect();
+        let joker = joker.map(|symbol| {
+            cards
+                .iter()
+                .find(|card| card.symbol == symbol)
+                .cloned()
+                .expect("Joker must be part of the deck")
+        });
+        Deck { cards, joker }
+    }
+
+    // Part 1: J is a Jack
+    fn jacks() -> Deck {
+        Deck::new("23456789TJQKA", None)
+    }
+
+    // Part 2: J is a Joker, and the weakest card on its own
+    fn jokers() -> Deck {
+        Deck::new("J23456789TQKA", Some('J'))
+    }
+
+    fn card(&self, symbol: char) -> Option<Card> {
+        self.cards
+            .iter()
+            .find(|card| card.symbol == symbol)
+            .cloned()
+    }
+
+    fn highest(&self) -> Card {
+        self.cards.last().cloned().expect("Deck has no cards")
+    }
+}
+
+impl Hand {
+    fn parse(line: &str, deck: &Deck) -> Result<Hand, ParseError> {
+        let mut parts = line.split(" ");
+
+        let cards: Vec<Card> = parts
+            .next()
+            .unwrap()
+            .chars()
+            .map(|symbol| deck.card(symbol).ok_or(ParseError::UnknownCard(symbol)))
+            .collect::<Result<Vec<Card>, ParseError>>()?;
+
+        let bid = parts
+            .next()
+            .ok_or_else(|| ParseError::MissingBid(line.to_string()))?
+            .parse()
+            .map_err(|_| ParseError::InvalidBid(line.to_string()))?;
+
+        let rank = Self::rank(&cards, deck);
+        let jokers_as = Self::jokers_as(&cards, deck);
+
+        Ok(Hand {
+            cards,
+            bid,
+            rank,
+            jokers_as,
+        })
+    }
+
+    fn counts<'a>(cards: &'a [Card], deck: &Deck) -> HashMap<&'a Card, u32> {
+        // Count every card which is not a joker
+        cards
+            .iter()
+            .filter(|card| Some(*card) != deck.joker.as_ref())
+            .fold(HashMap::new(), |mut map, card| {
+                *map.entry(card).or_insert(0) += 1;
+                map
+            })
+    }
+
+    fn rank(cards: &[Card], deck: &Deck) -> Rank {
+        let counts = Self::counts(cards, deck);
+        let jokers = cards.len() as u32 - counts.values().sum::<u32>();
+
+        // sort in decreasing value order
+        let mut sorted: Vec<u32> = counts.into_values().collect();
+        sorted.sort();
+        sorted.reverse();
+
+        // Jokers always join the largest group, if only jokers they are their own group
+        match sorted.first_mut() {
+            Some(largest) => *largest += jokers,
+            None => sorted.push(jokers),
+        }
+
+        Rank(sorted)
+    }
+
+    #[cfg(test)]
+    fn rank_brute_force(cards: &[Card], deck: &Deck) -> Rank {
+        // Reference for rank, try every other card in the deck in place of each joker and keep the best
+        let joker = match deck.joker.as_ref() {
+            Some(joker) => joker,
+            None => return Self::rank(cards, deck),
+        };
+
+        match cards.iter().position(|card| card == joker) {
+            None => Self::rank(cards, deck),
+            Some(index) => deck
+                .cards
+                .iter()
+                .filter(|card| *card != joker)
+                .map(|substitute| {
+                    let mut cards = cards.to_vec();
+                    cards[index] = substitute.clone();
+                    Self::rank_brute_force(&cards, deck)
+                })
+                .max()
+                .expect("Deck has no cards besides the joker"),
+        }
+    }
+
+    fn jokers_as(cards: &[Card], deck: &Deck) -> Option<Card> {
+        // Ties between groups are given to the highest card, and a hand of only jokers becomes the highest card
+        let joker = deck.joker.as_ref()?;
+        if !cards.contains(joker) {
+            return None;
+        }
+
+        let best = Self::counts(cards, deck)
+            .into_iter()
+            .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(a.cmp(b)))
+            .map(|(card, _)| card.clone())
+            .unwrap_or_else(|| deck.highest());
+        Some(best)
+    }
+
+    fn tiebreak(&self, weaker: &Hand) -> Option<usize> {
+        if self.rank != weaker.rank {
+            return None;
+        }
+        self.cards
+            .iter()
+            .zip(weaker.cards.iter())
+            .position(|(a, b)| a.cmp(b) != Ordering::Equal)
+    }
+}
+
+fn ranking(hands: &[Hand]) -> Vec<RankedHand<'_>> {
+    let mut sorted_hands: Vec<&Hand> = hands.iter().collect();
+    sorted_hands.sort();
+
+    sorted_hands
+        .iter()
+        .enumerate()
+        .map(|(index, hand)| RankedHand {
+            position: index as u32 + 1,
+            hand,
+            tiebreak: index
+                .checked_sub(1)
+                .and_then(|weaker| hand.tiebreak(sorted_hands[weaker])),
+            contribution: (index as u32 + 1) * hand.bid,
+        })
+        .collect()
+}
+
+fn winnings(hands: &[Hand]) -> u32 {
+    ranking(hands)
+        .iter()
+        .map(|ranked| {
+            trace!(
+                "{} {} ranks {} winning {}",
+                ranked.hand,
+                ranked.hand.rank,
+                ranked.position,
+                ranked.contribution
+            );
+            ranked.contribution
+        })
+        .sum()
+}
+
+fn ranking_table(ranked: &[RankedHand]) -> String {
+    let mut table = format!(
+        "{:>8}  {:<5}  {:<9}  {:<6}  {:<8}  {:>6}  {:>12}\n",
+        "Position", "Hand", "Rank", "Jokers", "Tiebreak", "Bid", "Contribution"
+    );
+    for row in ranked {
+        table += &format!(
+            "{:>8}  {:<5}  {:<9}  {:<6}  {:<8}  {:>6}  {:>12}\n",
+            row.position,
+            row.hand.to_string(),
+            row.hand.rank.to_string(),
+            row.hand
+                .jokers_as
+                .as_ref()
+                .map(|card| card.letter().to_string())
+                .unwrap_or_default(),
+            row.tiebreak
+                .map(|index| format!("card {}", index + 1))
+                .unwrap_or_default(),
+            row.hand.bid,
+            row.contribution,
+        );
+    }
+    table
+}
+
+fn ranking_csv(ranked: &[RankedHand]) -> String {
+    let mut csv = String::from("position,hand,rank,jokers_as,tiebreak,bid,contribution\n");
+    for row in ranked {
+        csv += &format!(
+            "{},{},{},{},{},{},{}\n",
+            row.position,
+            row.hand,
+            row.hand.rank,
+            row.hand
+                .jokers_as
+                .as_ref()
+                .map(|card| card.letter().to_string())
+                .unwrap_or_default(),
+            row.tiebreak
+                .map(|index| (index + 1).to_string())
+                .unwrap_or_default(),
+            row.hand.bid,
+            row.contribution,
+        );
+    }
+    csv
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    UnknownCard(char),
+    MissingBid(String),
+    InvalidBid(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownCard(symbol) => write!(f, "Could not parse card value '{}'", symbol),
+            ParseError::MissingBid(line) => write!(f, "Hand '{}' has no bid", line),
+            ParseError::InvalidBid(line) => {
+                write!(f, "Hand '{}' has a bid which is not a u32", line)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEXT: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_part_one() {
+        let deck = Deck::jacks();
+        let hands: Vec<Hand> = TEXT
+            .lines()
+            .map(|line| Hand::parse(line, &deck).unwrap())
+            .collect();
+
+        assert_eq!(winnings(&hands), 6440);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let deck = Deck::jokers();
+        let hands: Vec<Hand> = TEXT
+            .lines()
+            .map(|line| Hand::parse(line, &deck).unwrap())
+            .collect();
+
+        assert_eq!(winnings(&hands), 5905);
+        assert_eq!(part_two_brute_force(&parse(TEXT).unwrap()), 5905);
+    }
+
+    #[test]
+    fn test_ranking() {
+        let deck = Deck::jokers();
+        let hands: Vec<Hand> = TEXT
+            .lines()
+            .map(|line| Hand::parse(line, &deck).unwrap())
+            .collect();
+        let ranked = ranking(&hands);
+
+        let order: Vec<String> = ranked.iter().map(|row| row.hand.to_string()).collect();
+        assert_eq!(order, vec!["32T3K", "KK677", "T55J5", "QQQJA", "KTJJT"]);
+
+        // QQQJA beats T55J5 on the first card, both are Four with a joker
+        assert_eq!(ranked[3].hand.rank.to_string(), "Four");
+        assert_eq!(ranked[3].hand.jokers_as, deck.card('Q'));
+        assert_eq!(ranked[3].tiebreak, Some(0));
+        // KTJJT's jokers become tens
+        assert_eq!(ranked[4].hand.jokers_as, deck.card('T'));
+        assert_eq!(ranked[4].tiebreak, Some(0));
+        assert_eq!(ranked[4].contribution, 5 * 220);
+        assert_eq!(ranked[1].tiebreak, None);
+    }
+
+    #[test]
+    fn test_ranking_csv() {
+        let deck = Deck::jokers();
+        let hands: Vec<Hand> = TEXT
+            .lines()
+            .map(|line| Hand::parse(line, &deck).unwrap())
+            .collect();
+        let csv = ranking_csv(&ranking(&hands));
+
+        assert_eq!(csv.lines().nth(3), Some("3,T55J5,Four,5,,684,2052"));
+    }
+
+    #[test]
+    fn test_rank_brute_force() {
+        let deck = Deck::jokers();
+        for cards in [
+            "JJJJJ", "JJ234", "J2345", "22JJ3", "2233J", "AAKKJ", "T55J5",
+        ] {
+            let cards: Vec<Card> = cards
+                .chars()
+                .map(|symbol| deck.card(symbol).unwrap())
+                .collect();
+            assert_eq!(
+                Hand::rank(&cards, &deck),
+                Hand::rank_brute_force(&cards, &deck)
+            );
+        }
+    }
+
+    // Reference for rank of a five card hand without jokers. Every kind has its own number of
+    // matching pairs, a full house has the three of the trips and the one of the pair
+    fn rank_by_pairs(cards: &[Card]) -> Rank {
+        let pairs = (0..cards.len())
+            .flat_map(|a| (a + 1..cards.len()).map(move |b| (a, b)))
+            .filter(|(a, b)| cards[*a] == cards[*b])
+            .count();
+        Rank(match pairs {
+            0 => vec![1, 1, 1, 1, 1],
+            1 => vec![2, 1, 1, 1],
+            2 => vec![2, 2, 1],
+            3 => vec![3, 1, 1],
+            4 => vec![3, 2],
+            6 => vec![4, 1],
+            10 => vec![5],
+            _ => unreachable!("Five cards can't have {} matching pairs", pairs),
+        })
+    }
+
+    // Checks all 13^5 hands, run with `make verify`. Jacks are checked against counting pairs, and
+    // jokers against trying every substitute, which ends in hands the jacks check covers
+    #[test]
+    #[ignore]
+    fn test_rank_exhaustive() {
+        for deck in [Deck::jacks(), Deck::jokers()] {
+            let size = deck.cards.len();
+            for mut index in 0..size.pow(5) {
+                let cards: Vec<Card> = (0..5)
+                    .map(|_| {
+                        let card = deck.cards[index % size].clone();
+                        index /= size;
+                        card
+                    })
+                    .collect();
+                let reference = match deck.joker {
+                    None => rank_by_pairs(&cards),
+                    Some(_) => Hand::rank_brute_force(&cards, &deck),
+                };
+                assert_eq!(Hand::rank(&cards, &deck), reference, "{:?}", cards);
+            }
+        }
+    }
+
+    #[test]
+    fn test_variable_hand_size() {
+        let deck = Deck::jokers();
+        let rank = |cards: &str| Hand::parse(&format!("{cards} 1"), &deck).unwrap().rank;
+
+        assert_eq!(rank("222333"), Rank(vec![3, 3]));
+        assert_eq!(rank("22233J"), Rank(vec![4, 2]));
+        assert_eq!(rank("JJJJJJJ"), Rank(vec![7]));
+        assert!(rank("2222333") > rank("2223334"));
+        assert!(rank("AKQT982") < rank("2234567"));
+        assert_eq!(rank("2233").to_string(), "2+2");
+    }
+
+    #[test]
+    fn test_custom_deck() {
+        // A deck of only three symbols, where * is wild
+        let deck = Deck::new("*abc", Some('*'));
+        let hands: Vec<Hand> = "abc 1\n*ab 2\naab 3\nbba 4"
+            .lines()
+            .map(|line| Hand::parse(line, &deck).unwrap())
+            .collect();
+
+        let order: Vec<String> = ranking(&hands)
+            .iter()
+            .map(|row| row.hand.to_string())
+            .collect();
+        assert_eq!(order, vec!["abc", "*ab", "aab", "bba"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("32T3K 765\n32X3K 1").unwrap_err(),
+            ParseError::UnknownCard('X')
+        );
+        assert_eq!(
+            parse("32T3K").unwrap_err(),
+            ParseError::MissingBid("32T3K".to_string())
+        );
+        assert_eq!(
+            parse("32T3K -5").unwrap_err(),
+            ParseError::InvalidBid("32T3K -5".to_string())
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+
+use super::{trace, Direction, Map, NavigationError, NodeMatch};
+
+// Branch of a node which does not exist in the map
+const MISSING: u32 = u32::MAX;
+
+// The map with every label interned to a dense id, so walking never touches a String
+#[derive(Debug)]
+pub struct CompactMap {
+    // Label of every node, indexed by id
+    labels: Vec<String>,
+    ids: HashMap<String, u32>,
+    // Only the first ids have a line in the map, the rest are branch targets which were never defined
+    defined: usize,
+    // Branches of every node are targets[offsets[id]..offsets[id + 1]]
+    offsets: Vec<u32>,
+    targets: Vec<u32>,
+    // Interned label of every branch, in the same order as targets
+    branch_labels: Vec<u32>,
+    steps: Vec<Step>,
+    directions: Vec<Direction>,
+}
+
+#[derive(Debug)]
+enum Step {
+    Index(usize),
+    Label(u32),
+}
+
+// Positions after 2^k full cycles of the directions, for every node
+#[derive(Debug)]
+pub struct JumpTable<'a> {
+    map: &'a CompactMap,
+    levels: Vec<Vec<u32>>,
+}
+
+impl Map {
+    pub fn compact(&self) -> CompactMap {
+        // Sorted so ids are the same between runs
+        let mut labels: Vec<String> = self.nodes.keys().cloned().collect();
+        labels.sort();
+        let defined = labels.len();
+        let mut ids: HashMap<String, u32> = labels
+            .iter()
+            .enumerate()
+            .map(|(id, label)| (label.clone(), id as u32))
+            .collect();
+
+        let mut branch_ids: HashMap<&str, u32> = HashMap::new();
+        let mut offsets = vec![0];
+        let mut targets = Vec::new();
+        let mut branch_labels = Vec::new();
+        for id in 0..defined {
+            for branch in self.nodes[&labels[id]].iter() {
+                // Branches to nodes which were never defined get an id with no branches of their own
+                let target = match ids.get(&branch.target) {
+                    Some(target) => *target,
+                    None => {
+                        labels.push(branch.target.clone());
+                        ids.insert(branch.target.clone(), labels.len() as u32 - 1);
+                        labels.len() as u32 - 1
+                    }
+                };
+                let label = match branch.label.as_ref() {
+                    Some(label) => {
+                        let next = branch_ids.len() as u32;
+                        *branch_ids.entry(label).or_insert(next)
+                    }
+                    None => MISSING,
+                };
+                targets.push(target);
+                branch_labels.push(label);
+            }
+            offsets.push(targets.len() as u32);
+        }
+        offsets.resize(labels.len() + 1, targets.len() as u32);
+
+        let steps = self
+            .directions
+            .iter()
+            .map(|direction| match direction {
+                Direction::Index(index) => Step::Index(*index),
+                // A label no node uses can never be followed
+                Direction::Label(label) => {
+                    Step::Label(branch_ids.get(label.as_str()).cloned().unwrap_or(MISSING))
+                }
+            })
+            .collect();
+
+        CompactMap {
+            labels,
+            ids,
+            defined,
+            offsets,
+            targets,
+            branch_labels,
+            steps,
+            directions: self.directions.clone(),
+        }
+    }
+}
+
+impl CompactMap {
+    pub fn id(&self, label: &str) -> Option<u32> {
+        self.ids.get(label).cloned()
+    }
+
+    pub fn label(&self, id: u32) -> &str {
+        &self.labels[id as usize]
+    }
+
+    pub fn matching(&self, pattern: &NodeMatch) -> Vec<u32> {
+        (0..self.defined as u32)
+            .filter(|id| pattern.matches(self.label(*id)))
+            .collect()
+    }
+
+    fn next(&self, id: u32, step: usize) -> Result<u32, NavigationError> {
+        if id as usize >= self.defined {
+            return Err(NavigationError::UnknownNode(self.label(id).to_string()));
+        }
+
+        let start = self.offsets[id as usize] as usize;
+        let end = self.offsets[id as usize + 1] as usize;
+        let index = match self.steps[step % self.steps.len()] {
+            Step::Index(index) => Some(index),
+            Step::Label(label) => self.branch_labels[start..end]
+                .iter()
+                .position(|branch| *branch == label && label != MISSING),
+        };
+
+        match index.filter(|index| *index < end - start) {
+            Some(index) => Ok(self.targets[start + index]),
+            None => Err(NavigationError::UnknownBranch {
+                node: self.label(id).to_string(),
+                direction: self.directions[step % self.directions.len()].clone(),
+            }),
+        }
+    }
+
+    pub fn steps_to(&self, start: u32, end: &[bool]) -> Result<usize, NavigationError> {
+        if self.steps.is_empty() {
+            return Err(NavigationError::NoDirections);
+        }
+
+        // Walking is deterministic, so arriving at a node at the same point in the directions
+        // twice means the walk will repeat forever
+        let mut seen: HashMap<(u32, usize), usize> = HashMap::new();
+        let mut path: Vec<u32> = Vec::new();
+        let mut location = start;
+        for step in 0.. {
+            if let Some(loop_start) = seen.insert((location, step % self.steps.len()), step) {
+                return Err(NavigationError::NeverReaches {
+                    start: self.label(start).to_string(),
+                    loop_start,
+                    loop_nodes: path[loop_start..]
+                        .iter()
+                        .map(|id| self.label(*id).to_string())
+                        .collect(),
+                });
+            }
+            path.push(location);
+
+            let next = self.next(location, step)?;
+            trace!("{} -> {}", self.label(location), self.label(next));
+            location = next;
+            if end[location as usize] {
+                return Ok(step + 1);
+            }
+        }
+        unreachable!()
+    }
+
+    // Reference for the least common multiple of each ghost's steps, walks every ghost together
+    // until they are all on an end, which never returns if they never line up
+    #[cfg(test)]
+    pub fn ghost_steps_brute_force(
+        &self,
+        starts: &[u32],
+        end: &[bool],
+    ) -> Result<u64, NavigationError> {
+        if self.steps.is_empty() {
+            return Err(NavigationError::NoDirections);
+        }
+
+        let mut locations = starts.to_vec();
+        for step in 0.. {
+            for location in locations.iter_mut() {
+                *location = self.next(*location, step)?;
+            }
+            trace!(
+                "Ghosts on {:?} after {} steps",
+                locations
+                    .iter()
+                    .map(|id| self.label(*id))
+                    .collect::<Vec<&str>>(),
+                step + 1
+            );
+            if locations.iter().all(|location| end[*location as usize]) {
+                return Ok(step as u64 + 1);
+            }
+        }
+        unreachable!()
+    }
+
+    // Marks every id which matches the pattern, so walks only compare ids
+    pub fn ends(&self, pattern: &NodeMatch) -> Vec<bool> {
+        self.labels
+            .iter()
+            .map(|label| pattern.matches(label))
+            .collect()
+    }
+
+    // Builds enough levels to answer any query up to max_steps
+    pub fn jump_table(&self, max_steps: u64) -> Result<JumpTable<'_>, NavigationError> {
+        if self.steps.is_empty() {
+            return Err(NavigationError::NoDirections);
+        }
+
+        // Level 0 is the position after one full cycle of the directions
+        let cycle = (0..self.labels.len() as u32)
+            .map(|start| (0..self.steps.len()).try_fold(start, |id, step| self.next(id, step)))
+            .map(|end| end.unwrap_or(MISSING))
+            .collect();
+
+        let cycles = max_steps / self.steps.len() as u64;
+        let mut levels: Vec<Vec<u32>> = vec![cycle];
+        // Stops at the level for 2^63 cycles rather than overflowing the shift
+        while 1u64
+            .checked_shl(levels.len() as u32)
+            .is_some_and(|size| size <= cycles)
+        {
+            let last = levels.last().unwrap();
+            let next = last
+                .iter()
+                .map(|id| match id {
+                    &MISSING => MISSING,
+                    id => last[*id as usize],
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(JumpTable { map: self, levels })
+    }
+}
+
+impl<'a> JumpTable<'a> {
+    // Answers in O(log(steps / directions) + directions) instead of walking every step
+    pub fn position_after(&self, start: u32, steps: u64) -> Result<u32, NavigationError> {
+        let length = self.map.steps.len() as u64;
+        let mut cycles = steps / length;
+        let mut location = start;
+
+        let mut level = 0;
+        while cycles > 0 {
+            if cycles & 1 == 1 {
+                let table = self
+                    .levels
+                    .get(level)
+                    .expect("Jump table was not built for this many steps");
+                location = match table[location as usize] {
+                    // Walk the cycle normally to find which node is missing
+                    MISSING => return self.walk(location, length * (1 << level)),
+                    next => next,
+                };
+            }
+            cycles >>= 1;
+            level += 1;
+        }
+
+        self.walk(location, steps % length)
+    }
+
+    fn walk(&self, start: u32, steps: u64) -> Result<u32, NavigationError> {
+        (0..steps as usize).try_fold(start, |id, step| self.map.next(id, step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Map, NavigationError, NodeMatch};
+
+    static TEXT: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    #[test]
+    fn test_interned_walk() {
+        let map = Map::parse(TEXT).unwrap().compact();
+        let end = map.ends(&NodeMatch::Exact("ZZZ".to_string()));
+
+        assert_eq!(map.label(map.id("BBB").unwrap()), "BBB");
+        assert_eq!(map.steps_to(map.id("AAA").unwrap(), &end), Ok(6));
+    }
+
+    #[test]
+    fn test_position_after() {
+        let map = Map::parse(TEXT).unwrap().compact();
+        let table = map.jump_table(1000).unwrap();
+        let start = map.id("AAA").unwrap();
+
+        // Compare against walking every step
+        let mut location = start;
+        for steps in 0..1000 {
+            assert_eq!(table.position_after(start, steps), Ok(location));
+            location = map.next(location, steps as usize).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_huge_counts() {
+        // ZZZ's branches come after AAA's, so its index is added to a nonzero offset
+        let map = Map::parse("18446744073709551615\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)")
+            .unwrap()
+            .compact();
+        assert!(matches!(
+            map.next(map.id("ZZZ").unwrap(), 0),
+            Err(NavigationError::UnknownBranch { .. })
+        ));
+
+        let map = Map::parse("L\n\nAAA = (BBB, AAA)\nBBB = (AAA, BBB)")
+            .unwrap()
+            .compact();
+        let table = map.jump_table(u64::MAX).unwrap();
+        assert_eq!(table.levels.len(), 64);
+        assert_eq!(
+            table.position_after(map.id("AAA").unwrap(), u64::MAX),
+            Ok(map.id("BBB").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_missing_node() {
+        let map = Map::parse("L\n\nAAA = (QQQ, AAA)").unwrap().compact();
+        let table = map.jump_table(10).unwrap();
+
+        assert_eq!(
+            table.position_after(map.id("AAA").unwrap(), 5),
+            Err(NavigationError::UnknownNode("QQQ".to_string()))
+        );
+    }
+}
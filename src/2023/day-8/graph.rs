@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::Map;
+
+// The network of nodes without the instructions, each node points to every node it may step to
+#[derive(Debug)]
+pub struct Graph<'a> {
+    edges: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl Map {
+    pub fn graph(&self) -> Graph<'_> {
+        let edges = self
+            .nodes
+            .iter()
+            .map(|(id, branches)| {
+                (
+                    id.as_str(),
+                    branches
+                        .iter()
+                        .map(|branch| branch.target.as_str())
+                        .collect(),
+                )
+            })
+            .collect();
+        Graph { edges }
+    }
+}
+
+impl<'a> Graph<'a> {
+    fn neighbors(&self, node: &str) -> &[&'a str] {
+        self.edges
+            .get(node)
+            .map(|next| next.as_slice())
+            .unwrap_or(&[])
+    }
+
+    // Sorted so every query is deterministic
+    fn ids(&self) -> Vec<&'a str> {
+        let mut ids: Vec<&'a str> = self.edges.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    pub fn reachable(&self, start: &str) -> HashSet<&'a str> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<&'a str> = match self.edges.get_key_value(start) {
+            Some((id, _)) => vec![id],
+            None => return seen,
+        };
+        while let Some(node) = stack.pop() {
+            if seen.insert(node) {
+                stack.extend(
+                    self.neighbors(node)
+                        .iter()
+                        .filter(|next| !seen.contains(*next)),
+                );
+            }
+        }
+        seen
+    }
+
+    pub fn unreachable(&self, start: &str) -> Vec<&'a str> {
+        let reachable = self.reachable(start);
+        self.ids()
+            .into_iter()
+            .filter(|id| !reachable.contains(id))
+            .collect()
+    }
+
+    // Nodes which only lead back to themselves, like `DDD = (DDD, DDD)`
+    pub fn dead_ends(&self) -> Vec<&'a str> {
+        self.ids()
+            .into_iter()
+            .filter(|id| self.neighbors(id).iter().all(|next| next == id))
+            .collect()
+    }
+
+    // Kosaraju's algorithm, each component is sorted and components are ordered by their first node
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&'a str>> {
+        // First pass records the order nodes finish in
+        let mut finished: Vec<&'a str> = Vec::new();
+        let mut seen = HashSet::new();
+        for id in self.ids() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let mut stack = vec![(id, 0)];
+            while let Some((node, index)) = stack.pop() {
+                match self.neighbors(node).get(index) {
+                    Some(next) => {
+                        stack.push((node, index + 1));
+                        if self.edges.contains_key(next) && seen.insert(next) {
+                            stack.push((next, 0));
+                        }
+                    }
+                    None => finished.push(node),
+                }
+            }
+        }
+
+        // Second pass walks the reversed edges in reverse finish order
+        let mut reversed: HashMap<&'a str, Vec<&'a str>> = HashMap::new();
+        for (id, next) in self.edges.iter() {
+            for next in next {
+                reversed.entry(next).or_default().push(id);
+            }
+        }
+
+        let mut components = Vec::new();
+        let mut assigned = HashSet::new();
+        for id in finished.into_iter().rev() {
+            if !assigned.insert(id) {
+                continue;
+            }
+            let mut component = Vec::new();
+            let mut stack = vec![id];
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for previous in reversed.get(node).into_iter().flatten() {
+                    if assigned.insert(previous) {
+                        stack.push(previous);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components.sort();
+        components
+    }
+
+    // Breadth first search which ignores the instructions, so any branch may be taken at every node
+    pub fn shortest_path(&self, start: &str, end: &str) -> Option<Vec<&'a str>> {
+        let (start, _) = self.edges.get_key_value(start)?;
+        let mut previous: HashMap<&'a str, &'a str> = HashMap::new();
+        let mut queue = VecDeque::from([*start]);
+        let mut seen = HashSet::from([*start]);
+
+        while let Some(node) = queue.pop_front() {
+            if node == end {
+                let mut path = vec![node];
+                while let Some(prev) = previous.get(path.last().unwrap()) {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in self.neighbors(node) {
+                if seen.insert(next) {
+                    previous.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Map;
+
+    static TEXT: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    #[test]
+    fn test_reachable() {
+        let map = Map::parse(TEXT).unwrap();
+        let graph = map.graph();
+
+        assert_eq!(graph.reachable("AAA").len(), 7);
+        assert_eq!(graph.reachable("BBB").len(), 3);
+        assert_eq!(graph.unreachable("CCC"), vec!["AAA", "BBB", "DDD", "EEE"]);
+    }
+
+    #[test]
+    fn test_dead_ends() {
+        let map = Map::parse(TEXT).unwrap();
+
+        assert_eq!(map.graph().dead_ends(), vec!["DDD", "EEE", "GGG", "ZZZ"]);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let map = Map::parse(
+            "\
+LR
+
+AAA = (BBB, XXX)
+BBB = (CCC, XXX)
+CCC = (AAA, ZZZ)
+XXX = (XXX, XXX)
+ZZZ = (ZZZ, ZZZ)",
+        )
+        .unwrap();
+
+        assert_eq!(
+            map.graph().strongly_connected_components(),
+            vec![vec!["AAA", "BBB", "CCC"], vec!["XXX"], vec!["ZZZ"]]
+        );
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let map = Map::parse(TEXT).unwrap();
+        let graph = map.graph();
+
+        assert_eq!(
+            graph.shortest_path("AAA", "ZZZ"),
+            Some(vec!["AAA", "CCC", "ZZZ"])
+        );
+        assert_eq!(graph.shortest_path("BBB", "ZZZ"), None);
+        assert_eq!(graph.shortest_path("ZZZ", "ZZZ"), Some(vec!["ZZZ"]));
+    }
+}
@@ -0,0 +1,561 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+
+// Declared first so its macros reach the modules below
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+mod compact;
+mod graph;
+#[path = "../../inputs.rs"]
+mod inputs;
+#[path = "../../parallel.rs"]
+mod parallel;
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 8;
+static START_NODE: &str = "AAA";
+static END_NODE: &str = "ZZZ";
+
+fn main() {
+    println!("Hello Day 8!\n");
+
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let map = match parse(&input) {
+        Ok(map) => map,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let graph = map.graph();
+    println!(
+        "Network has {} nodes in {} strongly connected components, {} unreachable from AAA and {} dead ends\n",
+        map.nodes.len(),
+        graph.strongly_connected_components().len(),
+        graph.unreachable(START_NODE).len(),
+        graph.dead_ends().len(),
+    );
+
+    if let Some(path) = graph.shortest_path(START_NODE, END_NODE) {
+        println!(
+            "Ignoring directions, ZZZ is {} steps from AAA",
+            path.len() - 1
+        );
+    }
+    match part_one(&map) {
+        Ok(steps) => println!("There are {} steps to reach ZZZ", steps),
+        Err(err) => println!("{}", err),
+    }
+
+    // Ghosts start and end on any node matching these patterns, `?` is any character and `*` is any text
+    // Flags such as --input= or --trace are left for the input lookup and tracing
+    let patterns: Vec<String> = env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    let custom = !patterns.is_empty();
+    let mut args = patterns.into_iter();
+    let start = args
+        .next()
+        .map(|arg| NodeMatch::parse(&arg))
+        .unwrap_or(NodeMatch::Suffix("A".to_string()));
+    let end = args
+        .next()
+        .map(|arg| NodeMatch::parse(&arg))
+        .unwrap_or(NodeMatch::Suffix("Z".to_string()));
+    let ghost_steps = if custom {
+        map.count_ghost_steps(&start, &end)
+    } else {
+        part_two(&map)
+    };
+    match ghost_steps {
+        Ok(ghost_steps) => {
+            println!("There are {} ghost steps to reach {}", ghost_steps, end);
+
+            // The least common multiple assumes every ghost loops back to its end,
+            // jump each ghost ahead to check it really is on an end node
+            let compact = map.compact();
+            let arrived = compact.jump_table(ghost_steps).map(|table| {
+                compact.matching(&start).into_iter().all(|ghost| {
+                    table
+                        .position_after(ghost, ghost_steps)
+                        .map(|location| end.matches(compact.label(location)))
+                        .unwrap_or(false)
+                })
+            });
+            if arrived != Ok(true) {
+                println!("Not every ghost is on {} after {} steps", end, ghost_steps);
+            }
+        }
+        Err(err) => println!("{}", err),
+    }
+}
+
+pub(crate) fn parse(input: &str) -> Result<Map, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    let map = Map::parse(input)?;
+    verbose!(
+        "{} directions, {} nodes",
+        map.directions.len(),
+        map.nodes.len()
+    );
+    Ok(map)
+}
+
+pub(crate) fn part_one(map: &Map) -> Result<usize, NavigationError> {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    map.count_steps()
+}
+
+pub(crate) fn part_two(map: &Map) -> Result<u64, NavigationError> {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    map.count_ghost_steps(
+        &NodeMatch::Suffix("A".to_string()),
+        &NodeMatch::Suffix("Z".to_string()),
+    )
+}
+
+#[cfg(test)]
+pub(crate) fn part_two_brute_force(map: &Map) -> Result<u64, NavigationError> {
+    let start = NodeMatch::Suffix("A".to_string());
+    let map = map.compact();
+    let starts = map.matching(&start);
+    if starts.is_empty() {
+        return Err(NavigationError::NoStart(start.to_string()));
+    }
+    map.ghost_steps_brute_force(&starts, &map.ends(&NodeMatch::Suffix("Z".to_string())))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Direction {
+    // Branch by position, `L` and `R` are the first and second branch
+    Index(usize),
+    // Branch by the label a node gave it, such as `up` in `AAA = (up: BBB, down: CCC)`
+    Label(String),
+}
+
+#[derive(Debug)]
+struct Branch {
+    label: Option<String>,
+    target: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct Map {
+    directions: Vec<Direction>,
+    nodes: HashMap<String, Vec<Branch>>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum NodeMatch {
+    Exact(String),
+    Suffix(String),
+    // `?` matches any one character, `*` matches any run of characters
+    Pattern(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    MissingDirections,
+    // Node lines need an `=` between the id and its branches
+    MissingBranches { line: usize },
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum NavigationError {
+    UnknownNode(String),
+    UnknownBranch {
+        node: String,
+        direction: Direction,
+    },
+    NoStart(String),
+    NoDirections,
+    // The walk came back to a node at the same point in the directions without finding an end
+    NeverReaches {
+        start: String,
+        loop_start: usize,
+        loop_nodes: Vec<String>,
+    },
+}
+
+impl Direction {
+    fn parse(dir: &str) -> Direction {
+        match dir {
+            "L" => Direction::Index(0),
+            "R" => Direction::Index(1),
+            _ => match dir.parse::<usize>() {
+                Ok(index) => Direction::Index(index),
+                Err(_) => Direction::Label(dir.to_string()),
+            },
+        }
+    }
+
+    fn parse_all(line: &str) -> Vec<Direction> {
+        // `LRL` is one direction per character, `0 2 up` or a lone `up` separate each direction
+        if line.chars().all(|c| c == 'L' || c == 'R') {
+            line.chars()
+                .map(|dir| Direction::parse(&dir.to_string()))
+                .collect()
+        } else {
+            line.split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|dir| !dir.is_empty())
+                .map(Direction::parse)
+                .collect()
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::Index(index) => write!(f, "{}", index),
+            Direction::Label(label) => write!(f, "{}", label),
+        }
+    }
+}
+
+impl NodeMatch {
+    fn parse(text: &str) -> NodeMatch {
+        if text.contains(['?', '*']) {
+            NodeMatch::Pattern(text.to_string())
+        } else {
+            NodeMatch::Exact(text.to_string())
+        }
+    }
+
+    fn matches(&self, id: &str) -> bool {
+        match self {
+            NodeMatch::Exact(exact) => id == exact,
+            NodeMatch::Suffix(suffix) => id.ends_with(suffix.as_str()),
+            NodeMatch::Pattern(pattern) => {
+                let pattern: Vec<char> = pattern.chars().collect();
+                let id: Vec<char> = id.chars().collect();
+                Self::glob(&pattern, &id)
+            }
+        }
+    }
+
+    fn glob(pattern: &[char], id: &[char]) -> bool {
+        match (pattern.first(), id.first()) {
+            (None, None) => true,
+            (Some('*'), _) => {
+                Self::glob(&pattern[1..], id) || (!id.is_empty() && Self::glob(pattern, &id[1..]))
+            }
+            (Some('?'), Some(_)) => Self::glob(&pattern[1..], &id[1..]),
+            (Some(a), Some(b)) if a == b => Self::glob(&pattern[1..], &id[1..]),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for NodeMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeMatch::Exact(exact) => write!(f, "{}", exact),
+            NodeMatch::Suffix(suffix) => write!(f, "*{}", suffix),
+            NodeMatch::Pattern(pattern) => write!(f, "{}", pattern),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingDirections => write!(f, "Map should start with directions"),
+            ParseError::MissingBranches { line } => {
+                write!(f, "Line {} should be <id> = (<branch>, ...)", line)
+            }
+        }
+    }
+}
+
+impl fmt::Display for NavigationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NavigationError::UnknownNode(id) => write!(f, "Node {} does not exist", id),
+            NavigationError::UnknownBranch { node, direction } => {
+                write!(f, "Node {} has no branch {}", node, direction)
+            }
+            NavigationError::NoStart(start) => write!(f, "No node matches {}", start),
+            NavigationError::NoDirections => write!(f, "Map has no directions"),
+            NavigationError::NeverReaches {
+                start,
+                loop_start,
+                loop_nodes,
+            } => write!(
+                f,
+                "{} never reaches an end, from step {} it loops every {} steps through {}",
+                start,
+                loop_start,
+                loop_nodes.len(),
+                loop_nodes.join(" -> ")
+            ),
+        }
+    }
+}
+
+impl Map {
+    fn parse(text: &str) -> Result<Map, ParseError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty());
+        let (_, directions) = lines.next().ok_or(ParseError::MissingDirections)?;
+        let directions = Direction::parse_all(directions);
+
+        let mut nodes = HashMap::new();
+
+        for (index, text) in lines {
+            // Expect line to be in the following format
+            // <id> = (<branch>, ...) where a branch is <target> or <label>: <target>
+            let (id, branches) = text
+                .split_once('=')
+                .ok_or(ParseError::MissingBranches { line: index + 1 })?;
+            let id = id.trim();
+            let branches = branches
+                .trim()
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .split(',')
+                .map(|branch| match branch.split_once(':') {
+                    Some((label, target)) => Branch {
+                        label: Some(label.trim().to_string()),
+                        target: target.trim().to_string(),
+                    },
+                    None => Branch {
+                        label: None,
+                        target: branch.trim().to_string(),
+                    },
+                })
+                .filter(|branch| !branch.target.is_empty())
+                .collect();
+
+            nodes.insert(id.to_string(), branches);
+        }
+
+        Ok(Map { directions, nodes })
+    }
+
+    fn steps_to(&self, start: &str, end: &NodeMatch) -> Result<usize, NavigationError> {
+        let map = self.compact();
+        let start = map
+            .id(start)
+            .ok_or_else(|| NavigationError::UnknownNode(start.to_string()))?;
+        map.steps_to(start, &map.ends(end))
+    }
+
+    fn count_steps(&self) -> Result<usize, NavigationError> {
+        self.steps_to(START_NODE, &NodeMatch::Exact(END_NODE.to_string()))
+    }
+
+    fn count_ghost_steps(
+        &self,
+        start: &NodeMatch,
+        end: &NodeMatch,
+    ) -> Result<u64, NavigationError> {
+        let map = self.compact();
+        let ends = map.ends(end);
+
+        // Each ghost's path through the nodes will eventually reach a cycle
+        // We find the cycle length by finding the steps between start and end
+        // We then find the least common multiple of each cycle
+        // Which is the minimum number of steps for all cycles to reach their end state
+        // Each ghost walks alone, so with the parallel feature they walk on separate cores
+        let cycles = parallel::map(&map.matching(start), |location| {
+            let steps = map.steps_to(*location, &ends)?;
+            verbose!(
+                "Ghost from {} ends after {} steps",
+                map.label(*location),
+                steps
+            );
+            Ok(steps as u64)
+        })
+        .into_iter()
+        .collect::<Result<Vec<u64>, NavigationError>>()?;
+
+        cycles
+            .into_iter()
+            .reduce(lcm)
+            .ok_or_else(|| NavigationError::NoStart(start.to_string()))
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if a == 0 {
+        return b;
+    }
+    return gcd(b % a, a);
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    return (a * b) / gcd(a, b);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEXT: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    #[test]
+    fn test_part_one() {
+        let map = Map::parse(TEXT).unwrap();
+
+        assert_eq!(map.count_steps(), Ok(2));
+    }
+
+    static TEXT_TWO: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    #[test]
+    fn test_part_one_two() {
+        let map = Map::parse(TEXT_TWO).unwrap();
+
+        assert_eq!(map.count_steps(), Ok(6));
+    }
+
+    static TEXT_THREE: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_part_two() {
+        let map = Map::parse(TEXT_THREE).unwrap();
+
+        assert_eq!(
+            map.count_ghost_steps(
+                &NodeMatch::Suffix("A".to_string()),
+                &NodeMatch::Suffix("Z".to_string())
+            ),
+            Ok(6)
+        );
+        assert_eq!(part_two_brute_force(&map), Ok(6));
+    }
+
+    #[test]
+    fn test_node_match() {
+        assert!(NodeMatch::parse("AAA").matches("AAA"));
+        assert!(!NodeMatch::parse("AAA").matches("AAAA"));
+        assert!(NodeMatch::parse("*Z").matches("11Z"));
+        assert!(NodeMatch::parse("1?Z").matches("11Z"));
+        assert!(!NodeMatch::parse("1?Z").matches("22Z"));
+        assert!(NodeMatch::parse("*").matches(""));
+    }
+
+    #[test]
+    fn test_never_reaches() {
+        let map = Map::parse(TEXT).unwrap();
+
+        // BBB only leads to dead ends
+        assert_eq!(
+            map.steps_to("BBB", &NodeMatch::Exact("ZZZ".to_string())),
+            Err(NavigationError::NeverReaches {
+                start: "BBB".to_string(),
+                loop_start: 1,
+                loop_nodes: vec!["EEE".to_string(), "EEE".to_string()],
+            })
+        );
+        assert_eq!(
+            map.steps_to("QQQ", &NodeMatch::Exact("ZZZ".to_string())),
+            Err(NavigationError::UnknownNode("QQQ".to_string()))
+        );
+        assert_eq!(
+            map.count_ghost_steps(&NodeMatch::parse("*Q"), &NodeMatch::Suffix("Z".to_string())),
+            Err(NavigationError::NoStart("*Q".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_branch_indices() {
+        let map = Map::parse(
+            "\
+0 2 1
+
+AAA = (BBB, CCC, DDD)
+BBB = (AAA, AAA, CCC)
+CCC = (AAA, ZZZ, AAA)
+DDD = (AAA, AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ, ZZZ)",
+        )
+        .unwrap();
+
+        assert_eq!(map.count_steps(), Ok(3));
+    }
+
+    #[test]
+    fn test_branch_labels() {
+        let map = Map::parse(
+            "\
+up down
+
+AAA = (up: BBB, down: CCC)
+BBB = (down: ZZZ, up: AAA)
+CCC = (up: CCC, down: CCC)
+ZZZ = (up: ZZZ, down: ZZZ)",
+        )
+        .unwrap();
+
+        assert_eq!(map.count_steps(), Ok(2));
+
+        // A single label is one direction, not one per letter
+        assert_eq!(
+            Direction::parse_all("up"),
+            vec![Direction::Label("up".to_string())]
+        );
+        let map = Map::parse("up\n\nAAA = (up: ZZZ, down: AAA)\nZZZ = (up: ZZZ)").unwrap();
+        assert_eq!(map.count_steps(), Ok(1));
+
+        let map = Map::parse("up up\n\nAAA = (BBB, CCC)").unwrap();
+        assert_eq!(
+            map.count_steps(),
+            Err(NavigationError::UnknownBranch {
+                node: "AAA".to_string(),
+                direction: Direction::Label("up".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Map::parse("\n\n").unwrap_err(),
+            ParseError::MissingDirections
+        );
+        assert_eq!(
+            Map::parse("LR\n\nAAA = (BBB, BBB)\nBBB (AAA, AAA)").unwrap_err(),
+            ParseError::MissingBranches { line: 4 }
+        );
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::VecDeque;
+use std::env;
+use std::fmt;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+mod number;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+use self::number::{BigInt, Number};
+
+const YEAR: u16 = 2023;
+const DAY: u8 = 9;
+
+fn main() {
+    println!("Hello Day 9!\n");
+
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    // Pass --explain to see the difference table of every line
+    // Pass --arithmetic=i128 or --arithmetic=big when i64 overflows
+    // Pass --steps=<k> to also sum the values k steps past each end of every line
+    let args: Vec<String> = env::args().skip(1).collect();
+    let explain = args.iter().any(|arg| arg == "--explain");
+    let steps = args.iter().find_map(|arg| {
+        arg.strip_prefix("--steps=")
+            .map(|steps| steps.parse::<usize>().expect("Could not parse steps"))
+    });
+    let result = match args
+        .iter()
+        .find_map(|arg| arg.strip_prefix("--arithmetic="))
+    {
+        None | Some("i64") => parse(&input).and_then(|lines| solve(lines, explain, steps)),
+        Some("i128") => parse_lines::<i128>(&input).and_then(|lines| solve(lines, explain, steps)),
+        Some("big") => parse_lines::<BigInt>(&input).and_then(|lines| solve(lines, explain, steps)),
+        Some(other) => panic!("Unknown arithmetic '{}', expected i64, i128 or big", other),
+    };
+
+    if let Err(overflow) = result {
+        println!("{}", overflow);
+    }
+}
+
+pub(crate) fn parse(input: &str) -> Result<Vec<ReportLine>, Overflow> {
+    parse_lines::<i64>(input)
+}
+
+pub(crate) fn part_one<N: Number>(lines: &[ReportLine<N>]) -> Result<N, Overflow> {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    sum(lines
+        .iter()
+        .map(|report_line| (report_line.line, &report_line.prediction)))
+}
+
+pub(crate) fn part_two<N: Number>(lines: &[ReportLine<N>]) -> Result<N, Overflow> {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    sum(lines
+        .iter()
+        .map(|report_line| (report_line.line, &report_line.history)))
+}
+
+fn parse_lines<N: Number>(input: &str) -> Result<Vec<ReportLine<N>>, Overflow> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    let lines = input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| ReportLine::<N>::parse_checked(text, index + 1))
+        .collect::<Result<Vec<ReportLine<N>>, Overflow>>()?;
+    verbose!("{} lines", lines.len());
+    Ok(lines)
+}
+
+fn solve<N: Number>(
+    lines: Vec<ReportLine<N>>,
+    explain: bool,
+    steps: Option<usize>,
+) -> Result<(), Overflow> {
+    for (index, line) in lines.iter().enumerate() {
+        if line.degree().is_none() {
+            println!(
+                "Line {} never reaches all zeros, its extrapolation is meaningless",
+                index + 1
+            );
+        }
+    }
+
+    let prediction_sum = part_one(&lines)?;
+    println!("Sum of all predictions: {prediction_sum}");
+
+    let history_sum = part_two(&lines)?;
+    println!("Sum of all histories: {history_sum}");
+
+    if let Some(steps) = steps {
+        let prediction_sum = extension_sum(&lines, steps, Extend::Forward)?;
+        println!("Sum of all predictions {steps} steps ahead: {prediction_sum}");
+
+        let history_sum = extension_sum(&lines, steps, Extend::Backward)?;
+        println!("Sum of all histories {steps} steps back: {history_sum}");
+    }
+
+    if explain {
+        for line in lines.iter() {
+            println!("\n{}", line.render());
+        }
+    }
+    Ok(())
+}
+
+// The value furthest out after extending every line, lines with nothing to extend are left out
+fn extension_sum<N: Number>(
+    lines: &[ReportLine<N>],
+    steps: usize,
+    direction: Extend,
+) -> Result<N, Overflow> {
+    let extensions = lines
+        .iter()
+        .map(|line| Ok((line.line, line.extend(steps, direction)?)))
+        .collect::<Result<Vec<(usize, Vec<N>)>, Overflow>>()?;
+    sum(extensions.iter().filter_map(|(line, values)| {
+        let value = match direction {
+            Extend::Forward => values.last(),
+            Extend::Backward => values.first(),
+        };
+        value.map(|value| (*line, value))
+    }))
+}
+
+// Each value comes with the input line it's from
+fn sum<'a, N: Number + 'a>(
+    mut values: impl Iterator<Item = (usize, &'a N)>,
+) -> Result<N, Overflow> {
+    values.try_fold(N::zero(), |acc, (line, value)| {
+        acc.checked_add(value).ok_or(Overflow::Sum { line })
+    })
+}
+
+// Lines are 1 based lines of the input
+#[derive(Debug, PartialEq)]
+pub(crate) enum Overflow {
+    // Reading the line or working out a row of its difference table
+    Line { line: usize, row: usize },
+    // Adding the line's value to the total across every line
+    Sum { line: usize },
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Overflow::Line { line, row } => write!(
+                f,
+                "Overflow on line {} in row {} of its difference table, try a wider --arithmetic",
+                line, row
+            ),
+            Overflow::Sum { line } => write!(
+                f,
+                "Overflow adding line {} to the sum, try a wider --arithmetic",
+                line
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Extend {
+    Forward,
+    Backward,
+}
+
+pub(crate) struct ReportLine<N: Number = i64> {
+    // 1 based line of the input, used to report overflow
+    line: usize,
+    input: Vec<N>,
+    // The input, followed by the differences of each row, ending with a row of all zeros
+    accelerations: Vec<Vec<N>>,
+    prediction: N,
+    history: N,
+}
+
+#[cfg(test)]
+impl ReportLine {
+    fn parse(text: &str) -> ReportLine {
+        Self::parse_checked(text, 1).expect("Report line overflowed i64")
+    }
+}
+
+impl<N: Number> ReportLine<N> {
+    fn parse_checked(text: &str, line: usize) -> Result<ReportLine<N>, Overflow> {
+        // Anything which is not a number is skipped, but a number too large for N is an overflow
+        let input: Vec<N> = text
+            .split(" ")
+            .filter(|digit| BigInt::parse(digit).is_some())
+            .map(|digit| N::parse(digit).ok_or(Overflow::Line { line, row: 0 }))
+            .collect::<Result<Vec<N>, Overflow>>()?;
+
+        let mut accelerations: Vec<Vec<N>> = vec![input.to_owned()];
+
+        while accelerations
+            .last()
+            .unwrap()
+            .iter()
+            .any(|num| *num != N::zero())
+        {
+            let row = accelerations.len();
+            accelerations.push(
+                Self::acceleration(accelerations.last().unwrap())
+                    .ok_or(Overflow::Line { line, row })?,
+            );
+        }
+
+        // Ran out of values before reaching a row of zeros
+        if accelerations.len() > 1 && accelerations.last().unwrap().is_empty() {
+            accelerations.pop();
+        }
+
+        let extrapolated =
+            Self::extrapolate(&accelerations).map_err(|row| Overflow::Line { line, row })?;
+        let (history, prediction) = extrapolated
+            .first()
+            .cloned()
+            .unwrap_or((N::zero(), N::zero()));
+        trace!(
+            "Line {} has {} rows of differences, {} before and {} after",
+            line,
+            accelerations.len(),
+            history,
+            prediction
+        );
+
+        Ok(ReportLine {
+            line,
+            input,
+            accelerations,
+            prediction,
+            history,
+        })
+    }
+
+    fn acceleration(vec: &[N]) -> Option<Vec<N>> {
+        vec.windows(2)
+            .map(|slice| slice[1].checked_sub(&slice[0]))
+            .collect()
+    }
+
+    // The value before and after every row, working up from the last row
+    // Fails with the row which overflowed
+    fn extrapolate(accelerations: &[Vec<N>]) -> Result<Vec<(N, N)>, usize> {
+        let mut extrapolated: Vec<(N, N)> = Vec::with_capacity(accelerations.len());
+        for (row, values) in accelerations.iter().enumerate().rev() {
+            let (first, last) = match (values.first(), values.last()) {
+                (Some(first), Some(last)) => (first, last),
+                _ => continue,
+            };
+            let (before, after) = extrapolated
+                .last()
+                .cloned()
+                .unwrap_or((N::zero(), N::zero()));
+            extrapolated.push((
+                first.checked_sub(&before).ok_or(row)?,
+                last.checked_add(&after).ok_or(row)?,
+            ));
+        }
+        extrapolated.reverse();
+        Ok(extrapolated)
+    }
+
+    // The input with `steps` more values extrapolated onto one end
+    fn extend(&self, steps: usize, direction: Extend) -> Result<Vec<N>, Overflow> {
+        let mut rows: Vec<VecDeque<N>> = self
+            .accelerations
+            .iter()
+            .filter(|row| !row.is_empty())
+            .map(|row| row.iter().cloned().collect())
+            .collect();
+
+        for _ in 0..steps {
+            // Each row grows by the value just added to the row below it
+            // The last row is either all zeros, or assumed constant when it never reached zeros
+            let mut below: Option<N> = None;
+            for (row, values) in rows.iter_mut().enumerate().rev() {
+                let next = match (direction, below.as_ref()) {
+                    (Extend::Forward, None) => values.back().cloned(),
+                    (Extend::Backward, None) => values.front().cloned(),
+                    (Extend::Forward, Some(change)) => values.back().unwrap().checked_add(change),
+                    (Extend::Backward, Some(change)) => values.front().unwrap().checked_sub(change),
+                }
+                .ok_or(Overflow::Line {
+                    line: self.line,
+                    row,
+                })?;
+
+                match direction {
+                    Extend::Forward => values.push_back(next.clone()),
+                    Extend::Backward => values.push_front(next.clone()),
+                }
+                below = Some(next);
+            }
+        }
+
+        Ok(rows
+            .into_iter()
+            .next()
+            .map(|row| row.into_iter().collect())
+            .unwrap_or_default())
+    }
+
+    // Degree of the polynomial which generates the input, None if the rows never reach all zeros
+    fn degree(&self) -> Option<usize> {
+        let last = self.accelerations.last()?;
+        if last.is_empty() || last.iter().any(|num| *num != N::zero()) {
+            return None;
+        }
+        Some(self.accelerations.len().saturating_sub(2))
+    }
+
+    // The classic pyramid, with the extrapolated values in brackets
+    fn render(&self) -> String {
+        let extrapolated = Self::extrapolate(&self.accelerations).unwrap_or_default();
+        // Widest value with its brackets and a space either side, kept even so rows shift by half a cell
+        let widest = extrapolated
+            .iter()
+            .flat_map(|(before, after)| [before, after])
+            .chain(self.accelerations.iter().flatten())
+            .map(|num| num.to_string().len() + 2)
+            .max()
+            .unwrap_or(1);
+        let width = (widest + 2).div_ceil(2) * 2;
+
+        let mut text = String::new();
+        for (depth, (row, (before, after))) in self
+            .accelerations
+            .iter()
+            .zip(extrapolated.iter())
+            .enumerate()
+        {
+            let mut line = " ".repeat(depth * width / 2);
+            line += &format!("{:>width$}", format!("[{}]", before), width = width);
+            for num in row {
+                line += &format!("{:>width$}", num, width = width);
+            }
+            line += &format!("{:>width$}", format!("[{}]", after), width = width);
+            text += line.trim_end();
+            text += "\n";
+        }
+
+        match self.degree() {
+            Some(degree) => text += &format!("Degree {} from {} values", degree, self.input.len()),
+            None => text += &format!("Never reaches all zeros within {} values", self.input.len()),
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEXT: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn test_part_one() {
+        let lines: Vec<ReportLine> = TEXT.lines().map(ReportLine::parse).collect();
+
+        assert_eq!(lines[0].prediction, 18);
+        assert_eq!(lines[1].prediction, 28);
+        assert_eq!(lines[2].prediction, 68);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let lines: Vec<ReportLine> = TEXT.lines().map(ReportLine::parse).collect();
+
+        assert_eq!(lines[2].history, 5);
+    }
+
+    #[test]
+    fn test_extend() {
+        let lines: Vec<ReportLine> = TEXT.lines().map(ReportLine::parse).collect();
+
+        assert_eq!(
+            lines[0].extend(2, Extend::Forward),
+            Ok(vec![0, 3, 6, 9, 12, 15, 18, 21])
+        );
+        assert_eq!(
+            lines[1].extend(3, Extend::Backward),
+            Ok(vec![1, 0, 0, 1, 3, 6, 10, 15, 21])
+        );
+        assert_eq!(
+            lines[2].extend(1, Extend::Forward).unwrap().last(),
+            Some(&lines[2].prediction)
+        );
+        assert_eq!(
+            lines[2].extend(1, Extend::Backward).unwrap().first(),
+            Some(&lines[2].history)
+        );
+        assert_eq!(
+            lines[2].extend(0, Extend::Forward),
+            Ok(lines[2].input.clone())
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let text = "1 9223372036854775807 -9223372036854775808";
+
+        assert_eq!(
+            ReportLine::<i64>::parse_checked(text, 3).err(),
+            Some(Overflow::Line { line: 3, row: 1 })
+        );
+        assert_eq!(
+            ReportLine::<i64>::parse_checked("9223372036854775806 9223372036854775807", 2).err(),
+            Some(Overflow::Line { line: 2, row: 0 })
+        );
+        assert_eq!(
+            ReportLine::<i64>::parse_checked("99999999999999999999", 1).err(),
+            Some(Overflow::Line { line: 1, row: 0 })
+        );
+
+        let lines = parse("9223372036854775807\n1\n").unwrap();
+        assert_eq!(part_one(&lines), Err(Overflow::Sum { line: 2 }));
+        // The empty line has nothing to extend, but still counts as a line
+        let lines = parse("9223372036854775807\n\n1\n").unwrap();
+        assert_eq!(
+            extension_sum(&lines, 1, Extend::Forward),
+            Err(Overflow::Sum { line: 3 })
+        );
+        assert_eq!(
+            Overflow::Sum { line: 2 }.to_string(),
+            "Overflow adding line 2 to the sum, try a wider --arithmetic"
+        );
+
+        let line = ReportLine::<i128>::parse_checked(text, 3).unwrap();
+        assert_eq!(line.prediction, -55340232221128654844);
+
+        let line =
+            ReportLine::<BigInt>::parse_checked("99999999999999999999 199999999999999999999", 1)
+                .unwrap();
+        assert_eq!(line.prediction.to_string(), "299999999999999999999");
+        assert_eq!(line.history.to_string(), "-1");
+    }
+
+    #[test]
+    fn test_degree() {
+        let lines: Vec<ReportLine> = TEXT.lines().map(ReportLine::parse).collect();
+
+        assert_eq!(lines[0].degree(), Some(1));
+        assert_eq!(lines[1].degree(), Some(2));
+        assert_eq!(lines[2].degree(), Some(3));
+        assert_eq!(ReportLine::parse("0 0 0").degree(), Some(0));
+        assert_eq!(ReportLine::parse("1 2 4 8 16").degree(), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let line = ReportLine::parse("0 3 6 9 12 15");
+
+        assert_eq!(
+            line.render().lines().collect::<Vec<&str>>(),
+            vec![
+                "  [-3]     0     3     6     9    12    15  [18]",
+                "      [3]     3     3     3     3     3   [3]",
+                "         [0]     0     0     0     0   [0]",
+                "Degree 1 from 6 values",
+            ]
+        );
+    }
+}
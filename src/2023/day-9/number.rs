@@ -0,0 +1,219 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+// Arithmetic backend for the difference tables, every operation reports overflow instead of wrapping
+pub trait Number: Clone + PartialEq + fmt::Display + Sized {
+    fn parse(text: &str) -> Option<Self>;
+    fn zero() -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+}
+
+impl Number for i64 {
+    fn parse(text: &str) -> Option<i64> {
+        text.parse().ok()
+    }
+
+    fn zero() -> i64 {
+        0
+    }
+
+    fn checked_add(&self, other: &i64) -> Option<i64> {
+        i64::checked_add(*self, *other)
+    }
+
+    fn checked_sub(&self, other: &i64) -> Option<i64> {
+        i64::checked_sub(*self, *other)
+    }
+}
+
+impl Number for i128 {
+    fn parse(text: &str) -> Option<i128> {
+        text.parse().ok()
+    }
+
+    fn zero() -> i128 {
+        0
+    }
+
+    fn checked_add(&self, other: &i128) -> Option<i128> {
+        i128::checked_add(*self, *other)
+    }
+
+    fn checked_sub(&self, other: &i128) -> Option<i128> {
+        i128::checked_sub(*self, *other)
+    }
+}
+
+// Base for each limb of a BigInt, a power of 10 keeps Display simple
+const BASE: u32 = 1_000_000_000;
+
+// Arbitrary precision integer, only supporting what the difference tables need
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BigInt {
+    negative: bool,
+    // Least significant limb first, no trailing zero limbs, zero has no limbs
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    fn normalize(mut self) -> BigInt {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        if self.limbs.is_empty() {
+            self.negative = false;
+        }
+        self
+    }
+
+    fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut limbs = Vec::with_capacity(a.len().max(b.len()) + 1);
+        let mut carry = 0;
+        for i in 0..a.len().max(b.len()) {
+            let sum = a.get(i).unwrap_or(&0) + b.get(i).unwrap_or(&0) + carry;
+            limbs.push(sum % BASE);
+            carry = sum / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+        limbs
+    }
+
+    // Expects a to be at least as large as b
+    fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut limbs = Vec::with_capacity(a.len());
+        let mut borrow = 0;
+        for (i, limb) in a.iter().enumerate() {
+            let subtract = b.get(i).unwrap_or(&0) + borrow;
+            if *limb >= subtract {
+                limbs.push(limb - subtract);
+                borrow = 0;
+            } else {
+                limbs.push(limb + BASE - subtract);
+                borrow = 1;
+            }
+        }
+        limbs
+    }
+
+    fn add_signed(&self, other: &BigInt, other_negative: bool) -> BigInt {
+        if self.negative == other_negative {
+            return BigInt {
+                negative: self.negative,
+                limbs: Self::add_magnitude(&self.limbs, &other.limbs),
+            }
+            .normalize();
+        }
+        match Self::compare_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Less => BigInt {
+                negative: other_negative,
+                limbs: Self::sub_magnitude(&other.limbs, &self.limbs),
+            },
+            _ => BigInt {
+                negative: self.negative,
+                limbs: Self::sub_magnitude(&self.limbs, &other.limbs),
+            },
+        }
+        .normalize()
+    }
+}
+
+impl Number for BigInt {
+    fn parse(text: &str) -> Option<BigInt> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+            return None;
+        }
+
+        // Read 9 digits at a time from the least significant end
+        let limbs = digits
+            .as_bytes()
+            .rchunks(9)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |acc, digit| acc * 10 + (digit - b'0') as u32)
+            })
+            .collect();
+        Some(BigInt { negative, limbs }.normalize())
+    }
+
+    fn zero() -> BigInt {
+        BigInt {
+            negative: false,
+            limbs: Vec::new(),
+        }
+    }
+
+    fn checked_add(&self, other: &BigInt) -> Option<BigInt> {
+        Some(self.add_signed(other, other.negative))
+    }
+
+    fn checked_sub(&self, other: &BigInt) -> Option<BigInt> {
+        Some(self.add_signed(other, !other.negative))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut text = String::new();
+        if self.negative {
+            text.push('-');
+        }
+        match self.limbs.split_last() {
+            None => text.push('0'),
+            Some((most, rest)) => {
+                text += &most.to_string();
+                for limb in rest.iter().rev() {
+                    text += &format!("{:09}", limb);
+                }
+            }
+        }
+        // Pad so BigInt lines up in tables like the primitive numbers
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigInt, Number};
+
+    fn big(text: &str) -> BigInt {
+        BigInt::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_big_int() {
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(
+            big("999999999999999999")
+                .checked_add(&big("1"))
+                .unwrap()
+                .to_string(),
+            "1000000000000000000"
+        );
+        assert_eq!(
+            big("1000000000000000000")
+                .checked_sub(&big("1"))
+                .unwrap()
+                .to_string(),
+            "999999999999999999"
+        );
+        assert_eq!(big("5").checked_sub(&big("12")), Some(big("-7")));
+        assert_eq!(big("-5").checked_sub(&big("-12")), Some(big("7")));
+        assert_eq!(big("-5").checked_add(&big("5")), Some(BigInt::zero()));
+        assert_eq!(BigInt::parse("12a"), None);
+    }
+}
@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::time::Duration;
+
+use days::{self, Day};
+use generate::Rng;
+use inputs::Lookup;
+
+pub struct Options {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub runs: usize,
+    // Inputs to time instead of every day's input from the store
+    pub inputs: Vec<String>,
+    // A named input from the store, such as a teammate's
+    pub name: Option<String>,
+    // Size of a generated input to time as well
+    pub synthetic: Option<usize>,
+    pub seed: u64,
+    pub save: Option<String>,
+    pub baseline: Option<String>,
+    // Percentage the median may grow by before it counts as a regression
+    pub threshold: f64,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Options {
+        let mut options = Options {
+            year: None,
+            day: None,
+            runs: 10,
+            inputs: Vec::new(),
+            name: None,
+            synthetic: None,
+            seed: 0,
+            save: None,
+            baseline: None,
+            threshold: 10.0,
+        };
+
+        for arg in args {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, value),
+                None => panic!("Expected --flag=value, got {}", arg),
+            };
+            match flag {
+                "--year" => options.year = Some(value.parse().expect("Year is not a number")),
+                "--day" => options.day = Some(value.parse().expect("Day is not a number")),
+                "--runs" => options.runs = value.parse().expect("Runs is not a number"),
+                "--input" => options.inputs.push(value.to_string()),
+                "--name" => options.name = Some(value.to_string()),
+                "--synthetic" => {
+                    options.synthetic = Some(value.parse().expect("Size is not a number"))
+                }
+                "--seed" => options.seed = value.parse().expect("Seed is not a number"),
+                "--save" => options.save = Some(value.to_string()),
+                "--baseline" => options.baseline = Some(value.to_string()),
+                "--threshold" => {
+                    options.threshold = value.parse().expect("Threshold is not a number")
+                }
+                _ => panic!("Unknown flag {}", flag),
+            }
+        }
+        if options.runs == 0 {
+            panic!("Need at least one run");
+        }
+        options
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let nanos: Vec<f64> = sorted
+            .iter()
+            .map(|sample| sample.as_nanos() as f64)
+            .collect();
+        let mean = nanos.iter().sum::<f64>() / nanos.len() as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / nanos.len() as f64;
+
+        Stats {
+            mean: Duration::from_nanos(mean.round() as u64),
+            median,
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+// One timed phase of one day over one input
+#[derive(Debug, PartialEq)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub input: String,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+impl Measurement {
+    fn key(&self) -> (u16, u8, &str, &str) {
+        (self.year, self.day, &self.input, &self.phase)
+    }
+
+    // One line of a baseline file: year day input phase mean median stddev, times in nanoseconds
+    pub fn parse(line: &str) -> Measurement {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 7 {
+            panic!("Baseline line should have 7 fields: {}", line);
+        }
+        let nanos =
+            |field: &str| Duration::from_nanos(field.parse().expect("Time is not a number"));
+
+        Measurement {
+            year: fields[0].parse().expect("Year is not a number"),
+            day: fields[1].parse().expect("Day is not a number"),
+            input: fields[2].to_string(),
+            phase: fields[3].to_string(),
+            stats: Stats {
+                mean: nanos(fields[4]),
+                median: nanos(fields[5]),
+                stddev: nanos(fields[6]),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {}",
+            self.year,
+            self.day,
+            self.input,
+            self.phase,
+            self.stats.mean.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.stddev.as_nanos()
+        )
+    }
+}
+
+// A measurement beside the baseline's median for the same day, input and phase
+#[derive(Debug, PartialEq)]
+pub struct Comparison<'a> {
+    pub measurement: &'a Measurement,
+    pub baseline: Duration,
+}
+
+impl<'a> Comparison<'a> {
+    // How much the median grew, negative when it got faster
+    pub fn change(&self) -> f64 {
+        self.measurement.stats.median.as_nanos() as f64 / self.baseline.as_nanos().max(1) as f64
+            * 100.0
+            - 100.0
+    }
+}
+
+impl<'a> fmt::Display for Comparison<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} day {} {} {}: median {:?} was {:?} ({:+.1}%)",
+            self.measurement.year,
+            self.measurement.day,
+            self.measurement.input,
+            self.measurement.phase,
+            self.measurement.stats.median,
+            self.baseline,
+            self.change()
+        )
+    }
+}
+
+// Medians are compared since they ignore the odd slow run
+pub fn compare<'a>(
+    measurements: &'a [Measurement],
+    baseline: &[Measurement],
+) -> Vec<Comparison<'a>> {
+    let baseline: HashMap<(u16, u8, &str, &str), Duration> = baseline
+        .iter()
+        .map(|measurement| (measurement.key(), measurement.stats.median))
+        .collect();
+
+    measurements
+        .iter()
+        .filter_map(|measurement| {
+            Some(Comparison {
+                measurement,
+                baseline: *baseline.get(&measurement.key())?,
+            })
+        })
+        .collect()
+}
+
+pub fn regressions<'a>(
+    measurements: &'a [Measurement],
+    baseline: &[Measurement],
+    threshold: f64,
+) -> Vec<Comparison<'a>> {
+    compare(measurements, baseline)
+        .into_iter()
+        .filter(|comparison| comparison.change() > threshold)
+        .collect()
+}
+
+// The reference is only timed when asked, since on a real input it may take hours
+fn measure(day: &Day, name: &str, input: &str, runs: usize, reference: bool) -> Vec<Measurement> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut part_one = Vec::with_capacity(runs);
+    let mut part_two = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let run = (day.solve)(input);
+        parse.push(run.parse);
+        part_one.push(run.part_one.1);
+        part_two.push(run.part_two.1);
+    }
+
+    let mut phases = vec![
+        ("parse", parse),
+        ("part_one", part_one),
+        ("part_two", part_two),
+    ];
+    if let (true, Some(solve)) = (reference, day.reference) {
+        phases.push(("reference", (0..runs).map(|_| solve(input).1).collect()));
+    }
+    phases
+        .into_iter()
+        .map(|(phase, samples)| Measurement {
+            year: day.year,
+            day: day.day,
+            input: name.to_string(),
+            phase: phase.to_string(),
+            stats: Stats::from_samples(&samples),
+        })
+        .collect()
+}
+
+// Returns false if any phase regressed against the baseline
+pub fn run(options: &Options) -> bool {
+    let mut measurements = Vec::new();
+    for day in days::select(options.year, options.day) {
+        let inputs: Vec<String> = if options.inputs.is_empty() {
+            let lookup = Lookup {
+                name: options.name.clone(),
+                ..Lookup::from_env()
+            };
+            match lookup.find(day.year, day.day) {
+                Ok(path) => vec![path.to_string_lossy().into_owned()],
+                Err(err) => {
+                    println!("Skipping {} day {}: {}", day.year, day.day, err);
+                    Vec::new()
+                }
+            }
+        } else {
+            options.inputs.clone()
+        };
+
+        for path in inputs {
+            let input = match fs::read_to_string(&path) {
+                Ok(input) => input,
+                Err(err) => {
+                    println!(
+                        "Skipping {} day {}, can't read {}: {}",
+                        day.year, day.day, path, err
+                    );
+                    continue;
+                }
+            };
+            // Names have no spaces so baseline lines stay whitespace separated
+            let name = path.replace(char::is_whitespace, "_");
+            measurements.extend(measure(day, &name, &input, options.runs, false));
+        }
+
+        // Synthetic inputs can be made small enough for the reference
+        if let (Some(size), Some(generate)) = (options.synthetic, day.generate) {
+            let input = generate(&mut Rng::new(options.seed), size);
+            let name = format!("synthetic-{}-{}", size, options.seed);
+            measurements.extend(measure(day, &name, &input, options.runs, true));
+        }
+    }
+
+    println!(
+        "{:>4} {:>3}  {:<30} {:<9} {:>12} {:>12} {:>12}",
+        "Year", "Day", "Input", "Phase", "Mean", "Median", "Stddev"
+    );
+    for measurement in measurements.iter() {
+        println!(
+            "{:>4} {:>3}  {:<30} {:<9} {:>12} {:>12} {:>12}",
+            measurement.year,
+            measurement.day,
+            measurement.input,
+            measurement.phase,
+            format!("{:?}", measurement.stats.mean),
+            format!("{:?}", measurement.stats.median),
+            format!("{:?}", measurement.stats.stddev)
+        );
+    }
+
+    if let Some(path) = options.save.as_ref() {
+        let text: String = measurements
+            .iter()
+            .map(|measurement| format!("{}\n", measurement))
+            .collect();
+        fs::write(path, text).expect("Can't write baseline");
+        println!("Saved baseline to {}", path);
+    }
+
+    match options.baseline.as_ref() {
+        Some(path) => {
+            let baseline: Vec<Measurement> = fs::read_to_string(path)
+                .expect("Can't read baseline")
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(Measurement::parse)
+                .collect();
+            for comparison in compare(&measurements, &baseline) {
+                println!("{}", comparison);
+            }
+            let regressions = regressions(&measurements, &baseline, options.threshold);
+            for regression in regressions.iter() {
+                println!("Regression: {}", regression);
+            }
+            println!(
+                "{} regressions above {}% against {}",
+                regressions.len(),
+                options.threshold,
+                path
+            );
+            regressions.is_empty()
+        }
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, regressions, Measurement, Stats};
+    use std::time::Duration;
+
+    fn nanos(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(
+            Stats::from_samples(&nanos(&[2, 4, 4, 4, 5, 5, 7, 9])),
+            Stats {
+                mean: Duration::from_nanos(5),
+                median: Duration::from_nanos(4),
+                stddev: Duration::from_nanos(2),
+            }
+        );
+        assert_eq!(
+            Stats::from_samples(&nanos(&[30, 10, 20])).median,
+            Duration::from_nanos(20)
+        );
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = vec![
+            Measurement::parse("2023 5 input.txt part_one 100 100 3"),
+            Measurement::parse("2023 5 input.txt part_two 100 100 3"),
+        ];
+        let measurements = vec![
+            Measurement::parse("2023 5 input.txt part_one 105 109 3"),
+            Measurement::parse("2023 5 input.txt part_two 120 120 3"),
+            // No baseline to compare with
+            Measurement::parse("2023 6 input.txt part_two 900 900 3"),
+            Measurement::parse("2024 5 input.txt part_two 900 900 3"),
+        ];
+
+        let found = regressions(&measurements, &baseline, 10.0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].measurement, &measurements[1]);
+        assert_eq!(found[0].baseline, Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = vec![Measurement::parse(
+            "2023 5 synthetic-1000-0 reference 400 400 3",
+        )];
+        let measurements = vec![
+            Measurement::parse("2023 5 synthetic-1000-0 reference 100 100 3"),
+            Measurement::parse("2023 5 synthetic-1000-0 part_two 100 100 3"),
+        ];
+
+        let found = compare(&measurements, &baseline);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].change(), -75.0);
+        assert_eq!(
+            found[0].to_string(),
+            "2023 day 5 synthetic-1000-0 reference: median 100ns was 400ns (-75.0%)"
+        );
+        assert!(regressions(&measurements, &baseline, 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_baseline_line() {
+        let line = "2023 9 ../2023/day-9/input.txt parse 1200 1100 50";
+        assert_eq!(Measurement::parse(line).to_string(), line);
+    }
+}
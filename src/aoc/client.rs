@@ -0,0 +1,760 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use inputs::{self, Lookup};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// Advent of Code asks automated tools to say who runs them
+const USER_AGENT: &str = "github.com/Brian-MacMonigle/advent-of-code";
+
+// Kept in each year's directory of the input store, holding the Unix time in milliseconds that the
+// site's wait ends, so the next aoc submit waits too
+const WAIT_FILE: &str = "submit-wait.txt";
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+// What the site said about a submitted answer
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    // Answered too recently, nothing was checked
+    Wait(Duration),
+    // The part was already solved, or part two is not unlocked yet
+    WrongLevel,
+    Unknown(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "Wrong, the answer is too high"),
+            Verdict::TooLow => write!(f, "Wrong, the answer is too low"),
+            Verdict::Wrong => write!(f, "Wrong answer"),
+            Verdict::Wait(wait) => write!(f, "Answered too recently, wait {}s", wait.as_secs()),
+            Verdict::WrongLevel => write!(f, "Part is already solved or not unlocked yet"),
+            Verdict::Unknown(text) => write!(f, "Unexpected response: {}", text),
+        }
+    }
+}
+
+// The text of the <article> the site wraps its reply in, without tags
+fn article_text(html: &str) -> String {
+    let article = match html.split_once("<article>") {
+        Some((_, rest)) => rest.split("</article>").next().unwrap_or(rest),
+        None => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+// Reads waits like "You have 5m 3s left to wait", "Please wait one minute" or "wait 5 minutes"
+fn parse_wait(text: &str) -> Option<Duration> {
+    if text.contains("wait one minute") {
+        return Some(Duration::from_secs(60));
+    }
+    if let Some((_, rest)) = text.split_once("wait ") {
+        if let Some((minutes, _)) = rest.split_once(" minutes") {
+            if let Ok(minutes) = minutes.parse::<u64>() {
+                return minutes.checked_mul(60).map(Duration::from_secs);
+            }
+        }
+    }
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .map(|part| {
+            // The text comes from the page, so the unit may be any character or none at all
+            let unit = part.chars().last()?;
+            let number: u64 = part[..part.len() - unit.len_utf8()].parse().ok()?;
+            match unit {
+                'h' => number.checked_mul(3600),
+                'm' => number.checked_mul(60),
+                's' => Some(number),
+                _ => None,
+            }
+        })
+        .try_fold(0u64, |total, seconds| total.checked_add(seconds?))
+        .map(Duration::from_secs)
+}
+
+pub fn parse_verdict(html: &str) -> Verdict {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::Wait(parse_wait(&text).unwrap_or(Duration::from_secs(60)))
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ClientError {
+    MissingSession,
+    InvalidUrl(String),
+    Io(String),
+    // The site answers 400 or 500 when the session cookie is missing or expired
+    LoggedOut,
+    Status { status: u16, body: String },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => {
+                write!(
+                    f,
+                    "Set AOC_SESSION to the session cookie of a logged in browser"
+                )
+            }
+            ClientError::InvalidUrl(url) => write!(f, "Can't use url {}", url),
+            ClientError::Io(err) => write!(f, "Request failed: {}", err),
+            ClientError::LoggedOut => write!(f, "Session was rejected, it may have expired"),
+            ClientError::Status { status, body } => {
+                write!(f, "Request failed with {}: {}", status, body.trim())
+            }
+        }
+    }
+}
+
+fn io_error(err: std::io::Error) -> ClientError {
+    ClientError::Io(err.to_string())
+}
+
+// Plain HTTP/1.1, enough for a local server
+fn send_http(
+    url: &str,
+    method: &str,
+    headers: &[(&str, String)],
+    body: Option<&str>,
+) -> Result<Response, ClientError> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| ClientError::InvalidUrl(url.to_string()))?;
+    let (host, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        method, path, host
+    );
+    for (name, value) in headers {
+        request += &format!("{}: {}\r\n", name, value);
+    }
+    if let Some(body) = body {
+        request += &format!("Content-Length: {}\r\n", body.len());
+    }
+    request += "\r\n";
+    request += body.unwrap_or("");
+
+    let mut stream = TcpStream::connect(address).map_err(io_error)?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(30)))
+        .map_err(io_error)?;
+    stream.write_all(request.as_bytes()).map_err(io_error)?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(io_error)?;
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| ClientError::Io("Response has no headers".to_string()))?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| ClientError::Io(format!("Bad status line: {}", head)))?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+// A quoted value in a curl config file
+fn curl_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// The headers and body as a curl config, which goes to curl's stdin so the session cookie never
+// shows up in the process list
+fn curl_config(headers: &[(&str, String)], body: Option<&str>) -> String {
+    let mut config = String::new();
+    for (name, value) in headers {
+        config += &format!("header = {}\n", curl_quote(&format!("{}: {}", name, value)));
+    }
+    if let Some(body) = body {
+        config += &format!("data = {}\n", curl_quote(body));
+    }
+    config
+}
+
+// The real site only speaks HTTPS, which std can't, so curl makes those requests
+fn send_curl(
+    url: &str,
+    method: &str,
+    headers: &[(&str, String)],
+    body: Option<&str>,
+) -> Result<Response, ClientError> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--request", method])
+        .args(["--write-out", "\n%{http_code}"])
+        .args(["--config", "-"])
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(io_error)?;
+    // Dropping stdin closes it, which is when curl stops reading the config
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(curl_config(headers, body).as_bytes())
+            .map_err(io_error)?;
+    }
+    let output = child.wait_with_output().map_err(io_error)?;
+    if !output.status.success() {
+        return Err(ClientError::Io(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let output = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = output
+        .rsplit_once('\n')
+        .ok_or_else(|| ClientError::Io("curl gave no status".to_string()))?;
+    Ok(Response {
+        status: status
+            .trim()
+            .parse()
+            .map_err(|_| ClientError::Io(format!("Bad status {}", status)))?,
+        body: body.to_string(),
+    })
+}
+
+fn wait_path(store: &Path, year: u16) -> PathBuf {
+    store.join(year.to_string()).join(WAIT_FILE)
+}
+
+// Every year's wait saved by an earlier run
+fn saved_waits(store: &Path) -> HashMap<u16, SystemTime> {
+    fs::read_dir(store)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let year: u16 = entry.ok()?.file_name().to_str()?.parse().ok()?;
+            let until = fs::read_to_string(wait_path(store, year)).ok()?;
+            let millis: u64 = until.trim().parse().ok()?;
+            Some((year, UNIX_EPOCH + Duration::from_millis(millis)))
+        })
+        .collect()
+}
+
+pub struct Client {
+    pub url: String,
+    session: String,
+    // The input store, which the waits are saved in
+    store: PathBuf,
+    // Set for a year when the site asks us to wait, submissions before then are refused without a
+    // request
+    not_before: HashMap<u16, SystemTime>,
+}
+
+impl Client {
+    pub fn new(url: &str, session: &str, store: &Path) -> Client {
+        Client {
+            url: url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            store: store.to_path_buf(),
+            not_before: saved_waits(store),
+        }
+    }
+
+    // AOC_SESSION holds the session cookie, AOC_URL points somewhere other than the real site
+    pub fn from_env(store: &Path) -> Result<Client, ClientError> {
+        let session = env::var("AOC_SESSION").map_err(|_| ClientError::MissingSession)?;
+        let url = env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+        Ok(Client::new(&url, session.trim(), store))
+    }
+
+    // How much longer the site wants us to wait before answering for the year
+    fn wait(&self, year: u16) -> Option<Duration> {
+        let until = self.not_before.get(&year)?;
+        until.duration_since(SystemTime::now()).ok()
+    }
+
+    fn save_wait(&mut self, year: u16, wait: Duration) -> Result<(), ClientError> {
+        let until = SystemTime::now() + wait;
+        self.not_before.insert(year, until);
+        let millis = until
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis());
+        let path = wait_path(&self.store, year);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(path, millis.to_string()).map_err(io_error)
+    }
+
+    fn request(&self, method: &str, path: &str, body: Option<&str>) -> Result<String, ClientError> {
+        let url = format!("{}{}", self.url, path);
+        let mut headers = vec![
+            ("Cookie", format!("session={}", self.session)),
+            ("User-Agent", USER_AGENT.to_string()),
+        ];
+        if body.is_some() {
+            headers.push((
+                "Content-Type",
+                "application/x-www-form-urlencoded".to_string(),
+            ));
+        }
+
+        let response = if url.starts_with("http://") {
+            send_http(&url, method, &headers, body)?
+        } else {
+            send_curl(&url, method, &headers, body)?
+        };
+        match response.status {
+            200 => Ok(response.body),
+            400 | 500 => Err(ClientError::LoggedOut),
+            status => Err(ClientError::Status {
+                status,
+                body: response.body,
+            }),
+        }
+    }
+
+    pub fn download(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        self.request("GET", &format!("/{}/day/{}/input", year, day), None)
+    }
+
+    // The input from the store, downloading it into the store first if it isn't there yet
+    pub fn fetch_input(
+        &self,
+        lookup: &Lookup,
+        year: u16,
+        day: u8,
+        name: &str,
+    ) -> Result<String, ClientError> {
+        let path = lookup.path(year, day, name);
+        // aoc new leaves an empty placeholder, which still needs downloading
+        if inputs::saved(&path) {
+            return fs::read_to_string(&path).map_err(io_error);
+        }
+        let input = self.download(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(&path, &input).map_err(io_error)?;
+        Ok(input)
+    }
+
+    pub fn submit(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, ClientError> {
+        if let Some(wait) = self.wait(year) {
+            return Ok(Verdict::Wait(wait));
+        }
+
+        let body = format!("level={}&answer={}", part, form_encode(answer));
+        let html = self.request(
+            "POST",
+            &format!("/{}/day/{}/answer", year, day),
+            Some(&body),
+        )?;
+        // Wrong answers also come with a wait before the next one is checked
+        let verdict = parse_verdict(&html);
+        if let Some(wait) = parse_wait(&article_text(&html)) {
+            self.save_wait(year, wait)?;
+        }
+        Ok(verdict)
+    }
+}
+
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{curl_config, form_encode, parse_verdict, send_curl, Client, ClientError, Verdict};
+    use inputs::Lookup;
+    use std::env;
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::process::{self, Command};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    // An empty input store of its own for every test
+    fn store(test: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-client-{}-{}", test, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    fn page(text: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            text
+        )
+    }
+
+    // Answers requests like the real site, and records every one it was sent
+    fn mock_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                // Read the headers, then however much body they promise
+                loop {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request).into_owned();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| line.strip_prefix("Content-Length: "))
+                            .map_or(0, |length| length.parse().unwrap());
+                        if body.len() >= length || read == 0 {
+                            break;
+                        }
+                    }
+                }
+                let request = String::from_utf8(request).unwrap();
+
+                let (status, body) = if !request.contains("Cookie: session=secret") {
+                    (
+                        400,
+                        "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+                            .to_string(),
+                    )
+                } else if request.starts_with("GET /2023/day/5/input ") {
+                    (200, "seeds: 79 14 55 13\n".to_string())
+                } else if request.starts_with("GET ") {
+                    (
+                        404,
+                        "Please don't repeatedly request this endpoint before it unlocks!"
+                            .to_string(),
+                    )
+                } else if request.ends_with("answer=35") {
+                    (
+                        200,
+                        page("That's the right answer! You are <em>one gold star</em> closer."),
+                    )
+                } else if request.ends_with("answer=99") {
+                    (200, page("That's not the right answer; your answer is too high. Please wait one minute before trying again."))
+                } else if request.ends_with("answer=1") {
+                    (
+                        200,
+                        page("That's not the right answer; your answer is too low."),
+                    )
+                } else if request.ends_with("answer=2") {
+                    (200, page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."))
+                } else {
+                    (200, page("You don't seem to be solving the right level.  Did you already complete it?"))
+                };
+                seen.lock().unwrap().push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            parse_verdict(&page("That's the right answer!")),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer.  If you're stuck...")),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You gave an answer too recently.  You have 2h 10s left to wait."
+            )),
+            Verdict::Wait(Duration::from_secs(7210))
+        );
+        assert_eq!(
+            parse_verdict("<p>Something else</p>"),
+            Verdict::Unknown("Something else".to_string())
+        );
+        assert_eq!(
+            super::parse_wait("Please wait 5 minutes"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(super::parse_wait("You have 5m… left to wait"), None);
+        assert_eq!(super::parse_wait("You have 5é left to wait"), None);
+        assert_eq!(
+            super::parse_wait("You have 99999999999999999h left to wait"),
+            None
+        );
+        assert_eq!(
+            super::parse_wait("You have 5000000000000000h 5000000000000000h left to wait"),
+            None
+        );
+        assert_eq!(form_encode("-12 a+b"), "-12%20a%2Bb");
+    }
+
+    #[test]
+    fn test_download() {
+        let (url, requests) = mock_server();
+        let root = store("download");
+        let client = Client::new(&url, "secret", &root);
+
+        assert_eq!(
+            client.download(2023, 5),
+            Ok("seeds: 79 14 55 13\n".to_string())
+        );
+        assert!(matches!(
+            client.download(2023, 25),
+            Err(ClientError::Status { status: 404, .. })
+        ));
+        assert_eq!(
+            Client::new(&url, "expired", &root).download(2023, 5),
+            Err(ClientError::LoggedOut)
+        );
+
+        let request = requests.lock().unwrap()[0].clone();
+        assert!(request.contains("User-Agent: github.com/Brian-MacMonigle/advent-of-code"));
+    }
+
+    #[test]
+    fn test_fetch_input_caches() {
+        let (url, requests) = mock_server();
+        let root = store("caches");
+        let client = Client::new(&url, "secret", &root);
+        let lookup = Lookup {
+            root: root.clone(),
+            name: None,
+            file: None,
+        };
+
+        for _ in 0..2 {
+            assert_eq!(
+                client.fetch_input(&lookup, 2023, 5, "input"),
+                Ok("seeds: 79 14 55 13\n".to_string())
+            );
+        }
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert_eq!(
+            fs::read_to_string(root.join("2023/5/input.txt")).unwrap(),
+            "seeds: 79 14 55 13\n"
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_input_replaces_placeholder() {
+        let (url, requests) = mock_server();
+        let root = store("placeholder");
+        let client = Client::new(&url, "secret", &root);
+        let lookup = Lookup {
+            root: root.clone(),
+            name: None,
+            file: None,
+        };
+        // What aoc new writes before the puzzle unlocks
+        fs::create_dir_all(root.join("2023/5")).unwrap();
+        fs::write(root.join("2023/5/input.txt"), "").unwrap();
+
+        assert_eq!(
+            client.fetch_input(&lookup, 2023, 5, "input"),
+            Ok("seeds: 79 14 55 13\n".to_string())
+        );
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert_eq!(
+            fs::read_to_string(root.join("2023/5/input.txt")).unwrap(),
+            "seeds: 79 14 55 13\n"
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_curl_config() {
+        let headers = [
+            ("Cookie", "session=secret".to_string()),
+            ("X-Odd", "a \"quoted\" \\ value".to_string()),
+        ];
+        assert_eq!(
+            curl_config(&headers, Some("level=1&answer=35")),
+            "header = \"Cookie: session=secret\"\n\
+             header = \"X-Odd: a \\\"quoted\\\" \\\\ value\"\n\
+             data = \"level=1&answer=35\"\n"
+        );
+        assert_eq!(curl_config(&[], None), "");
+    }
+
+    #[test]
+    fn test_send_curl() {
+        // Nothing to check against without curl installed
+        if Command::new("curl").arg("--version").output().is_err() {
+            return;
+        }
+        let (url, requests) = mock_server();
+        let headers = [("Cookie", "session=secret".to_string())];
+
+        let response = send_curl(&format!("{}/2023/day/5/input", url), "GET", &headers, None);
+        assert_eq!(
+            response.map(|response| response.body),
+            Ok("seeds: 79 14 55 13\n".to_string())
+        );
+        let response = send_curl(
+            &format!("{}/2023/day/5/answer", url),
+            "POST",
+            &headers,
+            Some("level=1&answer=35"),
+        );
+        assert_eq!(response.map(|response| response.status), Ok(200));
+        assert!(requests.lock().unwrap()[1].ends_with("\r\n\r\nlevel=1&answer=35"));
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, requests) = mock_server();
+        let root = store("submit");
+        let mut client = Client::new(&url, "secret", &root);
+
+        assert_eq!(client.submit(2023, 5, 1, "35"), Ok(Verdict::Correct));
+        assert_eq!(client.submit(2023, 5, 1, "1"), Ok(Verdict::TooLow));
+        assert_eq!(client.submit(2023, 5, 1, "7"), Ok(Verdict::WrongLevel));
+        assert!(requests.lock().unwrap()[0].ends_with("\r\n\r\nlevel=1&answer=35"));
+
+        assert_eq!(
+            client.submit(2023, 5, 2, "2"),
+            Ok(Verdict::Wait(Duration::from_secs(65)))
+        );
+        // Waiting is remembered, so the next answer never reaches the site
+        assert!(matches!(
+            client.submit(2023, 5, 2, "35"),
+            Ok(Verdict::Wait(wait)) if wait <= Duration::from_secs(65)
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 4);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_wrong_answer_waits() {
+        let (url, requests) = mock_server();
+        let root = store("waits");
+        let mut client = Client::new(&url, "secret", &root);
+
+        assert_eq!(client.submit(2023, 5, 1, "99"), Ok(Verdict::TooHigh));
+        assert!(matches!(
+            client.submit(2023, 5, 1, "35"),
+            Ok(Verdict::Wait(wait)) if wait <= Duration::from_secs(60)
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_wait_outlives_the_client() {
+        let (url, requests) = mock_server();
+        let root = store("outlives");
+
+        // Every aoc submit is a new process with a new client
+        let mut first = Client::new(&url, "secret", &root);
+        assert_eq!(first.submit(2023, 5, 1, "99"), Ok(Verdict::TooHigh));
+        assert!(root.join("2023/submit-wait.txt").is_file());
+
+        let mut second = Client::new(&url, "secret", &root);
+        assert!(matches!(
+            second.submit(2023, 5, 1, "35"),
+            Ok(Verdict::Wait(wait)) if wait > Duration::ZERO && wait <= Duration::from_secs(60)
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        // Other years have waits of their own, and a wait which has ended is ignored
+        assert_eq!(second.submit(2022, 5, 1, "35"), Ok(Verdict::Correct));
+        fs::write(root.join("2023/submit-wait.txt"), "0").unwrap();
+        let mut third = Client::new(&url, "secret", &root);
+        assert_eq!(third.submit(2023, 5, 1, "35"), Ok(Verdict::Correct));
+        assert_eq!(requests.lock().unwrap().len(), 3);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
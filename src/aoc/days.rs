@@ -0,0 +1,228 @@
+use std::fmt;
+use std::time::Duration;
+
+use generate::Rng;
+
+// An answer, or the error a solution gave instead
+pub type Answer = Result<String, String>;
+
+// A part run on its own, and how long it took
+pub type TimedAnswer = (Answer, Duration);
+
+#[derive(Debug)]
+pub struct Run {
+    pub parse: Duration,
+    pub part_one: TimedAnswer,
+    pub part_two: TimedAnswer,
+}
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solve: fn(&str) -> Run,
+    // Only the parser, which must return an error rather than panic on any input
+    pub parse: fn(&str) -> Result<(), String>,
+    // Synthetic input, size is the main count of the day's format such as lines or cards
+    pub generate: Option<fn(&mut Rng, usize) -> String>,
+    // A slower part two kept to check the real one against, timed without the parse
+    pub reference: Option<fn(&str) -> TimedAnswer>,
+}
+
+// Anything a part may return
+pub trait IntoAnswer {
+    fn into_answer(self) -> Answer;
+}
+
+macro_rules! display_answer {
+    ($($type:ty),*) => {
+        $(impl IntoAnswer for $type {
+            fn into_answer(self) -> Answer {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answer!(u16, u32, u64, usize, i64);
+
+impl<T: fmt::Display, E: fmt::Display> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Answer {
+        self.map(|answer| answer.to_string())
+            .map_err(|err| err.to_string())
+    }
+}
+
+// Every day exposes parse, part_one and part_two, which are timed separately. A day with a
+// generator may also name its reference for part two
+macro_rules! day {
+    ($year:expr, $day:expr, $module:ident) => {
+        day!(@ $year, $day, $module, None, None)
+    };
+    ($year:expr, $day:expr, $module:ident, $generator:ident) => {
+        day!(@ $year, $day, $module, day!(@generate $generator), None)
+    };
+    ($year:expr, $day:expr, $module:ident, $generator:ident, $reference:ident) => {
+        day!(@ $year, $day, $module, day!(@generate $generator), Some(|input: &str| {
+            use std::time::{Duration, Instant};
+            use $crate::days::IntoAnswer;
+
+            match $module::parse(input) {
+                Ok(parsed) => {
+                    let start = Instant::now();
+                    let answer = $module::$reference(&parsed).into_answer();
+                    (answer, start.elapsed())
+                }
+                Err(err) => (Err(err.to_string()), Duration::ZERO),
+            }
+        }))
+    };
+    (@generate $generator:ident) => {
+        Some(|rng: &mut $crate::generate::Rng, size| {
+            $crate::generate::$generator::with_size(size).generate(rng)
+        })
+    };
+    (@ $year:expr, $day:expr, $module:ident, $generate:expr, $reference:expr) => {
+        $crate::days::Day {
+            year: $year,
+            day: $day,
+            generate: $generate,
+            reference: $reference,
+            parse: |input| $module::parse(input).map(|_| ()).map_err(|err| err.to_string()),
+            solve: |input| {
+                use std::time::{Duration, Instant};
+                use $crate::days::{IntoAnswer, Run};
+
+                let start = Instant::now();
+                let parsed = $module::parse(input);
+                let parse = start.elapsed();
+
+                // Neither part can run without a parsed input, so both report why
+                let parsed = match parsed {
+                    Ok(parsed) => parsed,
+                    Err(err) => {
+                        let err = Err(err.to_string());
+                        return Run {
+                            parse,
+                            part_one: (err.clone(), Duration::ZERO),
+                            part_two: (err, Duration::ZERO),
+                        };
+                    }
+                };
+
+                let start = Instant::now();
+                let answer = $module::part_one(&parsed).into_answer();
+                let part_one = (answer, start.elapsed());
+
+                let start = Instant::now();
+                let answer = $module::part_two(&parsed).into_answer();
+                let part_two = (answer, start.elapsed());
+
+                Run {
+                    parse,
+                    part_one,
+                    part_two,
+                }
+            },
+        }
+    };
+}
+
+// Every event keeps its days in a registry of its own, oldest first
+pub static YEARS: &[&[Day]] = &[
+    ::y2023::DAYS,
+    // aoc new adds years above this line
+];
+
+pub fn all() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|days| days.iter())
+}
+
+// The year a day belongs to when none is given
+pub fn latest_year() -> u16 {
+    all().map(|day| day.year).max().unwrap()
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    all().find(|registered| registered.year == year && registered.day == day)
+}
+
+// Days matching --year and --day, where either may be left out
+pub fn select(year: Option<u16>, day: Option<u8>) -> Vec<&'static Day> {
+    all()
+        .filter(|registered| year.is_none_or(|year| year == registered.year))
+        .filter(|registered| day.is_none_or(|day| day == registered.day))
+        .collect()
+}
+
+// Puzzles only go up to day 25, so a bigger number can only be a year
+const FIRST_YEAR: u16 = 2015;
+
+// The optional year and the day at the start of the arguments, and the arguments after them
+pub fn split_target(args: &[String]) -> Option<(Option<u16>, u8, &[String])> {
+    let first: u16 = args.first()?.parse().ok()?;
+    if first >= FIRST_YEAR {
+        let day = args.get(1)?.parse().ok()?;
+        Some((Some(first), day, &args[2..]))
+    } else {
+        Some((None, args[0].parse().ok()?, &args[1..]))
+    }
+}
+
+// The newest year with a solution for the day
+fn latest_year_with(years: &[&[Day]], day: u8) -> Option<u16> {
+    years
+        .iter()
+        .flat_map(|days| days.iter())
+        .filter(|registered| registered.day == day)
+        .map(|registered| registered.year)
+        .max()
+}
+
+// The year and day at the start of the arguments, and the arguments after them. Without a year
+// the latest one solving that day is used, so `5` and `2023 5` are the same day even once 2024
+// has begun. A day no year has solved yet belongs to the latest year
+pub fn parse_target(args: &[String]) -> Option<(u16, u8, &[String])> {
+    let (year, day, rest) = split_target(args)?;
+    let year = year
+        .or_else(|| latest_year_with(YEARS, day))
+        .unwrap_or_else(latest_year);
+    Some((year, day, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{latest_year_with, parse_target, split_target, Day, YEARS};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_target() {
+        let five = args(&["5", "--size=3"]);
+        assert_eq!(parse_target(&five), Some((2023, 5, &five[1..])));
+        let dated = args(&["2024", "1"]);
+        assert_eq!(parse_target(&dated), Some((2024, 1, &dated[2..])));
+        assert_eq!(split_target(&args(&["10"])), Some((None, 10, &[][..])));
+        assert_eq!(parse_target(&args(&["2024"])), None);
+        assert_eq!(parse_target(&args(&["day"])), None);
+    }
+
+    #[test]
+    fn test_latest_year_with() {
+        // Once aoc new 2024 1 has run, only day 1 belongs to 2024
+        let first = &YEARS[0][0];
+        let next: &[Day] = &[Day {
+            year: 2024,
+            day: 1,
+            solve: first.solve,
+            parse: first.parse,
+            generate: None,
+            reference: None,
+        }];
+        let years = [YEARS[0], next];
+        assert_eq!(latest_year_with(&years, 1), Some(2024));
+        assert_eq!(latest_year_with(&years, 9), Some(2023));
+        assert_eq!(latest_year_with(&years, 25), None);
+    }
+}
@@ -0,0 +1,253 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use days::{self, Day};
+use inputs;
+
+// Fixtures are laid out like the input store, fixtures/<year>/<day>/part-<part>.txt with the
+// expected answer beside it in part-<part>.answer
+pub const DEFAULT_ROOT: &str = "fixtures";
+
+// The checkout's fixtures from anywhere inside it, otherwise the fixtures directory here
+pub fn default_root() -> PathBuf {
+    inputs::repo_root()
+        .map(|root| root.join("src").join("aoc").join(DEFAULT_ROOT))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_ROOT))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+}
+
+// Everything between each open and close tag, in order
+fn between<'a>(html: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = html[offset..].find(open) {
+        let start = offset + start + open.len();
+        match html[start..].find(close) {
+            Some(end) => {
+                found.push((start, &html[start..start + end]));
+                offset = start + end + close.len();
+            }
+            None => break,
+        }
+    }
+    found
+}
+
+// Each part is an <article>, its answer is the last emphasized code in it and its input is the
+// last example block before that, so part two may reuse part one's example
+pub fn extract(html: &str) -> Vec<Example> {
+    let blocks = between(html, "<pre><code>", "</code></pre>");
+    between(html, "<article", "</article>")
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, (start, article))| {
+            let answers = between(article, "<code><em>", "</em></code>")
+                .into_iter()
+                .chain(between(article, "<em><code>", "</code></em>"));
+            let (at, answer) = answers.max_by_key(|(at, _)| *at)?;
+            let (_, input) = blocks.iter().rfind(|(block, _)| *block < start + at)?;
+            Some(Example {
+                part: index as u8 + 1,
+                input: strip_tags(input),
+                answer: strip_tags(answer),
+            })
+        })
+        .collect()
+}
+
+fn dir(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(year.to_string()).join(day.to_string())
+}
+
+pub fn write(root: &Path, year: u16, day: u8, examples: &[Example]) -> io::Result<Vec<PathBuf>> {
+    let dir = dir(root, year, day);
+    fs::create_dir_all(&dir)?;
+    let mut written = Vec::new();
+    for example in examples {
+        let input = dir.join(format!("part-{}.txt", example.part));
+        fs::write(&input, &example.input)?;
+        fs::write(
+            dir.join(format!("part-{}.answer", example.part)),
+            &example.answer,
+        )?;
+        written.push(input);
+    }
+    Ok(written)
+}
+
+// The day's fixtures by part, a fixture without an answer yet is left out like a missing one
+pub fn load(root: &Path, year: u16, day: u8) -> Vec<Example> {
+    (1..=2)
+        .filter_map(|part| {
+            let dir = dir(root, year, day);
+            let input = fs::read_to_string(dir.join(format!("part-{}.txt", part))).ok()?;
+            let answer = fs::read_to_string(dir.join(format!("part-{}.answer", part))).ok()?;
+            let answer = answer.trim();
+            if answer.is_empty() {
+                return None;
+            }
+            Some(Example {
+                part,
+                input,
+                answer: answer.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn check(day: &Day, example: &Example) -> Result<(), String> {
+    let run = (day.solve)(&example.input);
+    let answer = match example.part {
+        1 => run.part_one.0,
+        _ => run.part_two.0,
+    }?;
+    if answer == example.answer {
+        Ok(())
+    } else {
+        Err(format!("expected {}, got {}", example.answer, answer))
+    }
+}
+
+// Runs every fixture, returning false if any failed
+pub fn run(root: &Path, year: Option<u16>, day: Option<u8>) -> bool {
+    let mut passed = true;
+    for day in days::select(year, day) {
+        let examples = load(root, day.year, day.day);
+        if examples.is_empty() {
+            println!("{} day {}: no fixtures", day.year, day.day);
+        }
+        for example in examples {
+            match check(day, &example) {
+                Ok(()) => println!("{} day {} part {}: ok", day.year, day.day, example.part),
+                Err(err) => {
+                    println!(
+                        "{} day {} part {}: {}",
+                        day.year, day.day, example.part, err
+                    );
+                    passed = false;
+                }
+            }
+        }
+    }
+    passed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, extract, load, write, Example, DEFAULT_ROOT};
+    use days;
+    use inputs;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    // Laid out like a saved puzzle page once both parts are solved
+    static PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><body><main>
+<article class="day-desc"><h2>--- Day 2: Test ---</h2><p>For example:</p>
+<pre><code>1 &lt; 2
+<em>3</em> &amp; 4
+</code></pre>
+<p>Adding these gives <code><em>10</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Using the same example, <code>1</code> and <code>2</code> make <em><code>24</code></em>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_extract() {
+        let input = "1 < 2\n3 & 4\n".to_string();
+        assert_eq!(
+            extract(PAGE),
+            vec![
+                Example {
+                    part: 1,
+                    input: input.clone(),
+                    answer: "10".to_string(),
+                },
+                Example {
+                    part: 2,
+                    input,
+                    answer: "24".to_string(),
+                },
+            ]
+        );
+        assert_eq!(extract("<p>No puzzle here</p>"), vec![]);
+    }
+
+    #[test]
+    fn test_write_and_load() {
+        let root = env::temp_dir().join(format!("aoc-fixtures-{}", process::id()));
+        let examples = extract(PAGE);
+
+        write(&root, 2023, 2, &examples).unwrap();
+        assert_eq!(load(&root, 2023, 2), examples);
+        assert_eq!(load(&root, 2023, 3), vec![]);
+        assert_eq!(load(&root, 2024, 2), vec![]);
+
+        // Skeletons from aoc new have no answer until the example is filled in
+        fs::write(root.join("2023/2/part-2.answer"), "\n").unwrap();
+        assert_eq!(load(&root, 2023, 2).len(), 1);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_fixtures() {
+        // Found from the checkout, so running from elsewhere fails rather than checking nothing
+        let root = inputs::repo_root()
+            .expect("Run the tests from inside the repository")
+            .join("src")
+            .join("aoc")
+            .join(DEFAULT_ROOT);
+        for day in days::all() {
+            let examples = load(&root, day.year, day.day);
+            assert!(
+                !examples.is_empty(),
+                "{} day {} has no fixtures",
+                day.year,
+                day.day
+            );
+            for example in examples {
+                assert_eq!(
+                    check(day, &example),
+                    Ok(()),
+                    "{} day {} part {}",
+                    day.year,
+                    day.day,
+                    example.part
+                );
+            }
+        }
+    }
+}
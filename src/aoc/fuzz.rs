@@ -0,0 +1,286 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+use days::{self, Day};
+use generate::Rng;
+use pool::panic_message;
+
+// The examples from each day's puzzle text and inputs a parser must reject, which every mutation
+// starts from
+static CORPUS: [(u16, u8, &str); 14] = [
+    (2023, 1, include_str!("corpus/2023/1/example-1.txt")),
+    (2023, 1, include_str!("corpus/2023/1/example-2.txt")),
+    (2023, 2, include_str!("corpus/2023/2/example.txt")),
+    (2023, 3, include_str!("corpus/2023/3/example.txt")),
+    (2023, 4, include_str!("corpus/2023/4/example.txt")),
+    (2023, 5, include_str!("corpus/2023/5/example.txt")),
+    (2023, 5, include_str!("corpus/2023/5/no-seeds.txt")),
+    (2023, 5, include_str!("corpus/2023/5/odd-seeds.txt")),
+    (2023, 6, include_str!("corpus/2023/6/example.txt")),
+    (2023, 7, include_str!("corpus/2023/7/example.txt")),
+    (2023, 8, include_str!("corpus/2023/8/example-1.txt")),
+    (2023, 8, include_str!("corpus/2023/8/example-2.txt")),
+    (2023, 8, include_str!("corpus/2023/8/example-3.txt")),
+    (2023, 9, include_str!("corpus/2023/9/example.txt")),
+];
+
+// Pieces the parsers split on or choke on, from separators to numbers which overflow
+static TOKENS: [&str; 24] = [
+    "0",
+    "-1",
+    "-",
+    "+",
+    "255",
+    "256",
+    "4294967296",
+    "18446744073709551615",
+    "18446744073709551616",
+    "99999999999999999999999",
+    " ",
+    "  ",
+    "\n",
+    "\n\n",
+    "\r\n",
+    ":",
+    ";",
+    ",",
+    "|",
+    "=",
+    "(",
+    ")",
+    "one",
+    "é",
+];
+
+pub struct Options {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub iterations: usize,
+    pub seed: u64,
+    // Where inputs which made a parser panic are written
+    pub crashes: String,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Options {
+        let mut options = Options {
+            year: None,
+            day: None,
+            iterations: 10000,
+            seed: 0,
+            crashes: "crashes".to_string(),
+        };
+
+        for arg in args {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, value),
+                None => panic!("Expected --flag=value, got {}", arg),
+            };
+            match flag {
+                "--year" => options.year = Some(value.parse().expect("Year is not a number")),
+                "--day" => options.day = Some(value.parse().expect("Day is not a number")),
+                "--iterations" => {
+                    options.iterations = value.parse().expect("Iterations is not a number")
+                }
+                "--seed" => options.seed = value.parse().expect("Seed is not a number"),
+                "--crashes" => options.crashes = value.to_string(),
+                _ => panic!("Unknown flag {}", flag),
+            }
+        }
+        options
+    }
+}
+
+// An input which made a parser panic, and what it panicked with
+#[derive(Debug)]
+pub struct Crash {
+    pub year: u16,
+    pub day: u8,
+    pub input: String,
+    pub message: String,
+}
+
+pub fn corpus(year: u16, day: u8) -> Vec<&'static str> {
+    CORPUS
+        .iter()
+        .filter(|(corpus_year, corpus_day, _)| *corpus_year == year && *corpus_day == day)
+        .map(|(_, _, text)| *text)
+        .collect()
+}
+
+fn random_index(rng: &mut Rng, bytes: &[u8]) -> usize {
+    rng.below(bytes.len() as u64 + 1) as usize
+}
+
+// A range of at most 8 bytes within the input
+fn random_range(rng: &mut Rng, bytes: &[u8]) -> (usize, usize) {
+    let start = random_index(rng, bytes);
+    let end = (start + rng.range(1, 8) as usize).min(bytes.len());
+    (start, end)
+}
+
+// One small random edit, any other day's example may be spliced in
+fn mutate_once(rng: &mut Rng, bytes: &mut Vec<u8>) {
+    match rng.below(7) {
+        0 if !bytes.is_empty() => {
+            let index = rng.below(bytes.len() as u64) as usize;
+            bytes[index] = *rng.pick(b" \n:;,|=()0123456789-AZaz");
+        }
+        1 => {
+            let (start, end) = random_range(rng, bytes);
+            bytes.drain(start..end);
+        }
+        2 => {
+            let (start, end) = random_range(rng, bytes);
+            let copy = bytes[start..end].to_vec();
+            let at = random_index(rng, bytes);
+            bytes.splice(at..at, copy);
+        }
+        3 => {
+            let at = random_index(rng, bytes);
+            bytes.splice(at..at, rng.pick(&TOKENS).bytes());
+        }
+        4 => {
+            let at = random_index(rng, bytes);
+            bytes.truncate(at);
+        }
+        5 => {
+            let other = rng.pick(&CORPUS).2.as_bytes();
+            let at = random_index(rng, bytes);
+            let from = random_index(rng, other);
+            bytes.truncate(at);
+            bytes.extend_from_slice(&other[from..]);
+        }
+        _ => {
+            // Swap two lines, which breaks inputs that expect a header first
+            let text = String::from_utf8_lossy(bytes).into_owned();
+            let mut lines: Vec<&str> = text.split('\n').collect();
+            let a = rng.below(lines.len() as u64) as usize;
+            let b = rng.below(lines.len() as u64) as usize;
+            lines.swap(a, b);
+            *bytes = lines.join("\n").into_bytes();
+        }
+    }
+}
+
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut bytes = input.as_bytes().to_vec();
+    for _ in 0..rng.range(1, 4) {
+        mutate_once(rng, &mut bytes);
+    }
+    // Parsers take a &str, so invalid UTF-8 is replaced rather than fed in
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+// Feeds the examples and their mutations to the parser, keeping the first input for each panic message
+pub fn fuzz_day(day: &Day, iterations: usize, seed: u64) -> Vec<Crash> {
+    // A new day without examples yet is fuzzed from nothing
+    let mut seeds = corpus(day.year, day.day);
+    if seeds.is_empty() {
+        seeds.push("");
+    }
+    let mut rng = Rng::new(seed);
+    let mut crashes: Vec<Crash> = Vec::new();
+
+    for iteration in 0..iterations {
+        let input = match seeds.get(iteration) {
+            Some(example) => example.to_string(),
+            None => {
+                let example = *rng.pick(&seeds);
+                mutate(&mut rng, example)
+            }
+        };
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| (day.parse)(&input))) {
+            let message = panic_message(payload);
+            if crashes.iter().all(|crash| crash.message != message) {
+                crashes.push(Crash {
+                    year: day.year,
+                    day: day.day,
+                    input,
+                    message,
+                });
+            }
+        }
+    }
+    crashes
+}
+
+pub fn run(options: &Options) -> bool {
+    // The crashes are reported below, the default hook would print a backtrace hint for each one
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut crashes = Vec::new();
+    for day in days::select(options.year, options.day) {
+        let found = fuzz_day(day, options.iterations, options.seed);
+        println!(
+            "{} day {}: {} inputs, {} crashes",
+            day.year,
+            day.day,
+            options.iterations,
+            found.len()
+        );
+        crashes.extend(found);
+    }
+    panic::set_hook(hook);
+
+    if crashes.is_empty() {
+        return true;
+    }
+    fs::create_dir_all(&options.crashes).expect("Can't create crashes directory");
+    for (index, crash) in crashes.iter().enumerate() {
+        let path = Path::new(&options.crashes)
+            .join(format!("{}-day-{}-{}.txt", crash.year, crash.day, index));
+        fs::write(&path, &crash.input).expect("Can't write crash");
+        println!("{}: {}", path.display(), crash.message);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{corpus, fuzz_day, mutate};
+    use days;
+    use generate::Rng;
+
+    #[test]
+    fn test_corpus_parses() {
+        let rejected = [
+            include_str!("corpus/2023/5/no-seeds.txt"),
+            include_str!("corpus/2023/5/odd-seeds.txt"),
+        ];
+        for day in days::all() {
+            for example in corpus(day.year, day.day) {
+                let parsed = (day.parse)(example);
+                if rejected.contains(&example) {
+                    assert!(parsed.is_err(), "{} day {}", day.year, day.day);
+                } else {
+                    assert_eq!(parsed, Ok(()), "{} day {}", day.year, day.day);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutate_seeded() {
+        let example = corpus(2023, 2)[0];
+        let first = mutate(&mut Rng::new(3), example);
+        assert_eq!(first, mutate(&mut Rng::new(3), example));
+        assert_ne!(first, example);
+    }
+
+    #[test]
+    fn test_parsers_never_panic() {
+        for day in days::all() {
+            let crashes = fuzz_day(day, 2000, 0);
+            assert!(
+                crashes.is_empty(),
+                "{} day {}: {:?}",
+                day.year,
+                day.day,
+                crashes
+            );
+        }
+    }
+}
@@ -0,0 +1,663 @@
+use std::collections::HashSet;
+
+// Small seeded generator, the same seed always gives the same input on every platform
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    // splitmix64
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in 0..bound
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Empty range");
+        self.next_u64() % bound
+    }
+
+    // Uniform in low..=high
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+// Day 1: lines of letters, digits and spelled out digits, always with at least one real digit
+#[derive(Debug)]
+pub struct Calibration {
+    pub lines: usize,
+    pub length: usize,
+    // Chance of each piece being a spelled out digit
+    pub words: u64,
+}
+
+impl Calibration {
+    pub fn with_size(size: usize) -> Calibration {
+        Calibration {
+            lines: size,
+            length: 20,
+            words: 20,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        static WORDS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+
+        let mut text = String::new();
+        for _ in 0..self.lines {
+            let mut line = String::new();
+            while line.len() < self.length {
+                if rng.chance(self.words) {
+                    line += *rng.pick(&WORDS);
+                } else if rng.chance(20) {
+                    line.push((b'1' + rng.below(9) as u8) as char);
+                } else {
+                    line.push((b'a' + rng.below(26) as u8) as char);
+                }
+            }
+            let digit = (b'1' + rng.below(9) as u8) as char;
+            line.insert(rng.below(line.len() as u64 + 1) as usize, digit);
+            text += &line;
+            text.push('\n');
+        }
+        text
+    }
+}
+
+// Day 2: games of rounds drawing red, green and blue cubes
+#[derive(Debug)]
+pub struct CubeGames {
+    // Ids are read as u8
+    pub games: u8,
+    pub rounds: u64,
+    pub cubes: u8,
+}
+
+impl CubeGames {
+    pub fn with_size(size: usize) -> CubeGames {
+        CubeGames {
+            games: size.min(u8::MAX as usize) as u8,
+            rounds: 6,
+            cubes: 20,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut text = String::new();
+        for id in 1..=self.games {
+            let rounds: Vec<String> = (0..rng.range(1, self.rounds))
+                .map(|_| {
+                    let mut colors = vec!["red", "green", "blue"];
+                    rng.shuffle(&mut colors);
+                    colors.truncate(rng.range(1, 3) as usize);
+                    colors
+                        .iter()
+                        .map(|color| format!("{} {}", rng.range(1, self.cubes as u64), color))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                })
+                .collect();
+            text += &format!("Game {}: {}\n", id, rounds.join("; "));
+        }
+        text
+    }
+}
+
+// Day 3: grid of part numbers and symbols on a background of dots
+#[derive(Debug)]
+pub struct Schematic {
+    pub width: usize,
+    pub height: usize,
+    pub numbers: u64,
+    pub symbols: u64,
+}
+
+impl Schematic {
+    pub fn with_size(size: usize) -> Schematic {
+        Schematic {
+            width: size,
+            height: size,
+            numbers: 12,
+            symbols: 6,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        static SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '%', '=', '-', '&'];
+
+        let mut text = String::new();
+        for _ in 0..self.height {
+            let mut line = String::new();
+            // Numbers need something between them or they would merge into one
+            let mut after_number = false;
+            while line.len() < self.width {
+                let room = (self.width - line.len()) as u64;
+                if !after_number && rng.chance(self.numbers) {
+                    let digits = rng.range(1, room.min(3));
+                    line += &rng
+                        .range(10_u64.pow(digits as u32 - 1), 10_u64.pow(digits as u32) - 1)
+                        .to_string();
+                    after_number = true;
+                    continue;
+                }
+                if rng.chance(self.symbols) {
+                    line.push(*rng.pick(&SYMBOLS));
+                } else {
+                    line.push('.');
+                }
+                after_number = false;
+            }
+            text += &line;
+            text.push('\n');
+        }
+        text
+    }
+}
+
+// Day 4: scratchcards with an exact number of matches on each card
+#[derive(Debug)]
+pub struct Scratchcards {
+    pub cards: usize,
+    pub winning: usize,
+    pub provided: usize,
+    pub max_matches: usize,
+}
+
+impl Scratchcards {
+    pub fn with_size(size: usize) -> Scratchcards {
+        Scratchcards {
+            cards: size,
+            winning: 10,
+            provided: 25,
+            max_matches: 10,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        assert!(
+            self.winning + self.provided <= 99 && self.max_matches <= self.winning,
+            "Card numbers are 1 to 99"
+        );
+
+        // Won copies grow quickly, so matches are dropped once the total could overflow the u32 count
+        let budget = u32::MAX as u64 / 2;
+        let mut copies = vec![1_u64; self.cards + self.max_matches + 1];
+        let mut total = self.cards as u64;
+
+        let width = self.cards.to_string().len();
+        let mut text = String::new();
+        for card in 0..self.cards {
+            let mut matches = rng.range(0, self.max_matches.min(self.provided) as u64) as usize;
+            if total + copies[card] * matches as u64 > budget {
+                matches = 0;
+            }
+            total += copies[card] * matches as u64;
+            for won in card + 1..=card + matches {
+                copies[won] += copies[card];
+            }
+
+            let mut numbers: Vec<u64> = (1..=99).collect();
+            rng.shuffle(&mut numbers);
+            let winning = &numbers[..self.winning];
+            let mut provided: Vec<u64> = winning[..matches].to_vec();
+            provided.extend(&numbers[self.winning..self.winning + self.provided - matches]);
+            rng.shuffle(&mut provided);
+
+            let format = |numbers: &[u64]| {
+                numbers
+                    .iter()
+                    .map(|number| format!("{:>2}", number))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            };
+            text += &format!(
+                "Card {:>width$}: {} | {}\n",
+                card + 1,
+                format(winning),
+                format(&provided),
+                width = width
+            );
+        }
+        text
+    }
+}
+
+// Day 5: almanac where every map shuffles pieces of the same span of numbers
+#[derive(Debug)]
+pub struct Almanac {
+    pub seed_ranges: usize,
+    // Part two tries every seed, so this decides how long it takes
+    pub seed_range_length: u64,
+    pub span: u64,
+    pub ranges_per_map: usize,
+}
+
+impl Almanac {
+    pub fn with_size(size: usize) -> Almanac {
+        Almanac {
+            seed_ranges: 10,
+            seed_range_length: size as u64,
+            span: 1 << 32,
+            ranges_per_map: 30,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        static MAPS: [&str; 7] = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ];
+
+        let seeds: Vec<String> = (0..self.seed_ranges)
+            .map(|_| {
+                let start = rng.below(self.span - self.seed_range_length);
+                format!("{} {}", start, rng.range(1, self.seed_range_length))
+            })
+            .collect();
+        let mut text = format!("seeds: {}\n", seeds.join(" "));
+
+        for name in MAPS.iter() {
+            // Cut the span into pieces and send every piece to a different place
+            let mut cuts: Vec<u64> = (1..self.ranges_per_map)
+                .map(|_| rng.below(self.span))
+                .collect();
+            cuts.push(0);
+            cuts.push(self.span);
+            cuts.sort();
+            cuts.dedup();
+            let mut pieces: Vec<(u64, u64)> = cuts
+                .windows(2)
+                .map(|cut| (cut[0], cut[1] - cut[0]))
+                .collect();
+            rng.shuffle(&mut pieces);
+
+            let mut lines: Vec<String> = Vec::new();
+            let mut dest = 0;
+            for (source, length) in pieces {
+                // Unmapped pieces keep their numbers
+                if rng.chance(90) {
+                    lines.push(format!("{} {} {}", dest, source, length));
+                }
+                dest += length;
+            }
+            text += &format!("\n{} map:\n{}\n", name, lines.join("\n"));
+        }
+        text
+    }
+}
+
+// Day 6: races where every record was set by some hold time
+#[derive(Debug)]
+pub struct Races {
+    pub races: usize,
+    pub max_time: u64,
+}
+
+impl Races {
+    pub fn with_size(size: usize) -> Races {
+        // Part two reads every time as one number, which has to stay small enough to square
+        let races = size.clamp(1, 9);
+        Races {
+            races,
+            max_time: 10_u64.pow(9 / races as u32) - 1,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut times = Vec::new();
+        let mut distances = Vec::new();
+        for _ in 0..self.races {
+            let time = rng.range(1, self.max_time);
+            let hold = rng.below(time);
+            times.push(time.to_string());
+            distances.push((hold * (time - hold)).to_string());
+        }
+
+        // Reading the line as one kerned race must not overflow u64 when squared
+        assert!(
+            times.concat().len() <= 9,
+            "Kerned race time would be too long"
+        );
+
+        let width = times
+            .iter()
+            .chain(distances.iter())
+            .map(|value| value.len())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let line = |label: &str, values: &[String]| {
+            let values: String = values
+                .iter()
+                .map(|value| format!("{:>width$}", value, width = width))
+                .collect();
+            format!("{:<9}{}\n", label, values)
+        };
+        line("Time:", &times) + &line("Distance:", &distances)
+    }
+}
+
+// Day 7: camel card hands, repeating earlier cards to get more than high cards
+#[derive(Debug)]
+pub struct Hands {
+    pub hands: usize,
+    pub max_bid: u64,
+    pub repeat: u64,
+}
+
+impl Hands {
+    pub fn with_size(size: usize) -> Hands {
+        Hands {
+            hands: size,
+            max_bid: 1000,
+            repeat: 35,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        static CARDS: &[u8] = b"23456789TJQKA";
+        assert!(self.hands <= 13_usize.pow(5), "Not enough distinct hands");
+
+        // Winnings are a u32 sum of bid * rank
+        let n = self.hands as u64;
+        let max_bid = self
+            .max_bid
+            .min(2 * u32::MAX as u64 / (n * (n + 1)).max(1))
+            .max(1);
+
+        let mut seen = HashSet::new();
+        let mut text = String::new();
+        while seen.len() < self.hands {
+            let mut hand: Vec<u8> = Vec::new();
+            while hand.len() < 5 {
+                let card = if !hand.is_empty() && rng.chance(self.repeat) {
+                    *rng.pick(&hand)
+                } else {
+                    *rng.pick(CARDS)
+                };
+                hand.push(card);
+            }
+            let hand = String::from_utf8(hand).unwrap();
+            if seen.insert(hand.clone()) {
+                text += &format!("{} {}\n", hand, rng.range(1, max_bid));
+            }
+        }
+        text
+    }
+}
+
+// Day 8: ghost loops like the real puzzle, the loop of every __A start takes directions * prime
+// steps to reach its __Z node and then repeats
+#[derive(Debug)]
+pub struct Network {
+    // Rounded up to a prime
+    pub directions: u64,
+    pub ghosts: usize,
+    pub min_cycle: u64,
+}
+
+impl Network {
+    pub fn with_size(size: usize) -> Network {
+        Network {
+            directions: size as u64,
+            ghosts: 6,
+            min_cycle: 43,
+        }
+    }
+
+    fn is_prime(n: u64) -> bool {
+        n >= 2
+            && (2..)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+    }
+
+    fn next_prime(n: u64) -> u64 {
+        (n..).find(|n| Self::is_prime(*n)).unwrap()
+    }
+
+    // Encodes an index as letters, the last one from `last`
+    fn label(index: usize, width: usize, last: &[u8]) -> String {
+        let mut label = vec![last[index % last.len()]];
+        let mut rest = index / last.len();
+        for _ in 1..width {
+            label.push(b'A' + (rest % 26) as u8);
+            rest /= 26;
+        }
+        label.reverse();
+        String::from_utf8(label).unwrap()
+    }
+
+    // Steps for all ghosts to be on a __Z node at once
+    #[cfg(test)]
+    pub fn ghost_steps(&self) -> u64 {
+        self.cycles().iter().product::<u64>() * Self::next_prime(self.directions)
+    }
+
+    fn cycles(&self) -> Vec<u64> {
+        let mut cycles = Vec::new();
+        let mut prime = self.min_cycle;
+        while cycles.len() < self.ghosts {
+            prime = Self::next_prime(prime);
+            // The directions length is a factor of every loop already
+            if prime != Self::next_prime(self.directions) {
+                cycles.push(prime);
+            }
+            prime += 1;
+        }
+        cycles
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        assert!(
+            self.ghosts >= 1 && self.ghosts < 26 * 26,
+            "Need 1 to 675 ghosts"
+        );
+
+        let length = Self::next_prime(self.directions) as usize;
+        let directions: Vec<usize> = (0..length).map(|_| rng.below(2) as usize).collect();
+        let loops: Vec<usize> = self.cycles().iter().map(|c| *c as usize * length).collect();
+
+        // Everything but the __A and __Z nodes ends in B to Y, so no ghost starts or ends there
+        let middle: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY";
+        let total: usize = loops.iter().map(|nodes| nodes - 1).sum();
+        let mut width = 3;
+        while 26_usize.pow(width as u32 - 1) * middle.len() < total {
+            width += 1;
+        }
+        let mut next_label = 0;
+
+        let mut lines = Vec::new();
+        for (ghost, nodes) in loops.iter().enumerate() {
+            // Position 0 is the __Z node, which steps back into the loop like the start does
+            let mut labels = vec![match ghost {
+                0 => "Z".repeat(width),
+                _ => Self::label(ghost, width, b"Z"),
+            }];
+            for _ in 1..*nodes {
+                labels.push(Self::label(next_label, width, middle));
+                next_label += 1;
+            }
+            let start = match ghost {
+                0 => "A".repeat(width),
+                _ => Self::label(ghost, width, b"A"),
+            };
+
+            let branches = |position: usize, rng: &mut Rng| {
+                let mut branches = [
+                    labels[rng.below(*nodes as u64) as usize].as_str(),
+                    labels[rng.below(*nodes as u64) as usize].as_str(),
+                ];
+                branches[directions[position % length]] = &labels[(position + 1) % nodes];
+                format!("({}, {})", branches[0], branches[1])
+            };
+            lines.push(format!("{} = {}", start, branches(0, rng)));
+            for (position, label) in labels.iter().enumerate() {
+                lines.push(format!("{} = {}", label, branches(position, rng)));
+            }
+        }
+        rng.shuffle(&mut lines);
+
+        let directions: String = directions
+            .iter()
+            .map(|direction| ['L', 'R'][*direction])
+            .collect();
+        format!("{}\n\n{}\n", directions, lines.join("\n"))
+    }
+}
+
+// Day 9: every history is a polynomial, built up from a random last row of differences
+#[derive(Debug)]
+pub struct Reports {
+    pub lines: usize,
+    pub values: usize,
+    pub max_degree: usize,
+    pub max_difference: u64,
+}
+
+impl Reports {
+    pub fn with_size(size: usize) -> Reports {
+        Reports {
+            lines: size,
+            values: 21,
+            max_degree: 6,
+            max_difference: 10,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut text = String::new();
+        for _ in 0..self.lines {
+            let degree = rng.range(0, self.max_degree as u64) as usize;
+            // First value of every row of differences, the last row is constant
+            let mut firsts: Vec<i64> = (0..=degree)
+                .map(|_| rng.range(0, 2 * self.max_difference) as i64 - self.max_difference as i64)
+                .collect();
+
+            let mut values = Vec::new();
+            for _ in 0..self.values {
+                values.push(firsts[0].to_string());
+                for row in 0..degree {
+                    firsts[row] += firsts[row + 1];
+                }
+            }
+            text += &values.join(" ");
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Network, Rng};
+    use days;
+
+    #[test]
+    fn test_seeded() {
+        for generate in days::all().filter_map(|day| day.generate) {
+            assert_eq!(
+                generate(&mut Rng::new(7), 10),
+                generate(&mut Rng::new(7), 10)
+            );
+        }
+    }
+
+    // Every generated input should solve without panics or errors
+    #[test]
+    fn test_stress() {
+        for day in days::all() {
+            let generate = match day.generate {
+                Some(generate) => generate,
+                None => continue,
+            };
+            for seed in 0..5 {
+                let input = generate(&mut Rng::new(seed), 12);
+                let run = (day.solve)(&input);
+                assert!(
+                    run.part_one.0.is_ok(),
+                    "{} day {} seed {}",
+                    day.year,
+                    day.day,
+                    seed
+                );
+                assert!(
+                    run.part_two.0.is_ok(),
+                    "{} day {} seed {}",
+                    day.year,
+                    day.day,
+                    seed
+                );
+            }
+        }
+    }
+
+    // The reference is checked on inputs small enough for it, with or without the parallel feature
+    #[test]
+    fn test_reference() {
+        for day in days::all() {
+            let (generate, reference) = match (day.generate, day.reference) {
+                (Some(generate), Some(reference)) => (generate, reference),
+                _ => continue,
+            };
+            for seed in 0..3 {
+                let input = generate(&mut Rng::new(seed), 2000);
+                assert_eq!(
+                    reference(&input).0,
+                    (day.solve)(&input).part_two.0,
+                    "{} day {} seed {}",
+                    day.year,
+                    day.day,
+                    seed
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_network_answer() {
+        let network = Network {
+            directions: 10,
+            ghosts: 3,
+            min_cycle: 3,
+        };
+        let input = network.generate(&mut Rng::new(1));
+        let run = (days::find(2023, 8).unwrap().solve)(&input);
+
+        // Directions become 11 long and the loops 3, 5 and 7 times that
+        assert_eq!(network.ghost_steps(), 11 * 3 * 5 * 7);
+        assert_eq!(run.part_one.0, Ok((11 * 3).to_string()));
+        assert_eq!(run.part_two.0, Ok(network.ghost_steps().to_string()));
+    }
+}
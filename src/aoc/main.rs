@@ -0,0 +1,341 @@
+// The shared files in src are included here and by every day through y2023, each copy its own module
+#![allow(clippy::duplicate_mod)]
+
+use std::env;
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+mod bench;
+mod client;
+#[macro_use]
+mod days;
+mod examples;
+mod fuzz;
+mod generate;
+// Shared with the day binaries, which load their input straight from their own arguments
+#[allow(dead_code)]
+#[path = "../inputs.rs"]
+mod inputs;
+// The pool runs on the same threads as the days' parallel feature
+#[allow(dead_code)]
+#[path = "../parallel.rs"]
+mod parallel;
+mod pool;
+#[cfg(test)]
+mod property;
+mod report;
+mod scaffold;
+#[cfg(test)]
+mod shared;
+// Only for its tests in shared, the days each include their own
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../trace.rs"]
+mod trace;
+mod watch;
+// Each event's days, registered in days::YEARS
+mod y2023;
+// aoc new adds years above this line
+
+fn print_answer(part: &str, answer: &days::Answer) {
+    match answer {
+        Ok(answer) => println!("{}: {}", part, answer),
+        Err(err) => println!("{} failed: {}", part, err),
+    }
+}
+
+// The optional year and the day a command starts with, and the arguments after them
+fn target<'a>(args: &'a [String], usage: &str) -> (u16, u8, &'a [String]) {
+    days::parse_target(args).unwrap_or_else(|| panic!("{}", usage))
+}
+
+fn solution(year: u16, day: u8) -> &'static days::Day {
+    days::find(year, day).unwrap_or_else(|| panic!("No solution for {} day {}", year, day))
+}
+
+fn run(args: &[String]) {
+    if args.iter().any(|arg| arg == "--all") {
+        return run_all(args);
+    }
+    let (year, day, args) = target(
+        args,
+        "Usage: aoc run [year] <day> [input] [--name=NAME] [--inputs=DIR] [--json]",
+    );
+    let day = solution(year, day);
+    let json = args.iter().any(|arg| arg == "--json");
+
+    // A path given after the day wins over the store
+    let mut lookup = inputs::Lookup::from_args(args);
+    if let Some(path) = args.iter().find(|arg| !arg.starts_with("--")) {
+        lookup.file = Some(path.into());
+    }
+    let (path, input) = match lookup.open(day.year, day.day) {
+        Ok(found) => found,
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    let run = (day.solve)(&input);
+    // One record per part and line, in place of the prose below
+    if json {
+        for record in report::records(day, &run, &path.to_string_lossy()) {
+            println!("{}", record);
+        }
+        return;
+    }
+    println!("{} day {} ({:?} to parse)", day.year, day.day, run.parse);
+    print_answer("Part 1", &run.part_one.0);
+    print_answer("Part 2", &run.part_two.0);
+}
+
+// Every day, or every day of --year, side by side on a pool of --jobs threads
+fn run_all(args: &[String]) {
+    let mut year = None;
+    let mut jobs = pool::default_jobs();
+    for arg in args.iter() {
+        match arg.split_once('=') {
+            Some(("--year", value)) => year = Some(value.parse().expect("Year is not a number")),
+            Some(("--jobs", value)) => jobs = value.parse().expect("Jobs is not a number"),
+            _ => {}
+        }
+    }
+    let json = args.iter().any(|arg| arg == "--json");
+    let lookup = inputs::Lookup::from_args(args);
+    let selected = days::select(year, None);
+
+    // Panics are reported with their day below, the default hook would print each one as it happens
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let start = Instant::now();
+    let outcomes: Vec<report::Outcome> =
+        pool::map(&selected, jobs, |day| report::solve(day, &lookup))
+            .into_iter()
+            .map(|result| result.unwrap_or_else(report::Outcome::Panicked))
+            .collect();
+    let elapsed = start.elapsed();
+    panic::set_hook(hook);
+
+    if json {
+        for (day, outcome) in selected.iter().zip(outcomes.iter()) {
+            for record in outcome.records(day) {
+                println!("{}", record);
+            }
+        }
+    } else {
+        println!("{}", report::header());
+        for (day, outcome) in selected.iter().zip(outcomes.iter()) {
+            println!("{}", report::row(day, outcome));
+        }
+        println!(
+            "{} of {} days solved in {:?} with --jobs={}, {:?} spent solving",
+            outcomes.iter().filter(|outcome| outcome.is_ok()).count(),
+            outcomes.len(),
+            elapsed,
+            jobs.max(1),
+            report::busy(&outcomes)
+        );
+    }
+    if !outcomes.iter().all(report::Outcome::is_ok) {
+        process::exit(1);
+    }
+}
+
+fn generate(args: &[String]) {
+    let (year, day, args) = target(
+        args,
+        "Usage: aoc generate [year] <day> [--size=N] [--seed=N]",
+    );
+    let day = solution(year, day);
+
+    let mut size = 100;
+    let mut seed = 0;
+    for arg in args.iter() {
+        match arg.split_once('=') {
+            Some(("--size", value)) => size = value.parse().expect("Size is not a number"),
+            Some(("--seed", value)) => seed = value.parse().expect("Seed is not a number"),
+            _ => panic!("Unknown argument {}", arg),
+        }
+    }
+    match day.generate {
+        Some(generate) => print!("{}", generate(&mut generate::Rng::new(seed), size)),
+        None => println!("No generator for {} day {}", day.year, day.day),
+    }
+}
+
+fn download(args: &[String]) {
+    let (year, day, args) = target(
+        args,
+        "Usage: aoc download [year] <day> [--name=NAME] [--inputs=DIR]",
+    );
+    let lookup = inputs::Lookup::from_args(args);
+    let name = lookup
+        .name
+        .clone()
+        .unwrap_or_else(|| inputs::DEFAULT_NAME.to_string());
+
+    let result = client::Client::from_env(&lookup.root)
+        .and_then(|client| client.fetch_input(&lookup, year, day, &name));
+    match result {
+        Ok(_) => println!("Saved {}", lookup.path(year, day, &name).display()),
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn submit(args: &[String]) {
+    let usage = "Usage: aoc submit [year] <day> <part> [answer] [--name=NAME] [--inputs=DIR]";
+    let positional: Vec<String> = args
+        .iter()
+        .filter(|arg| !arg.starts_with("--"))
+        .cloned()
+        .collect();
+    let (year, day, positional) = target(&positional, usage);
+    let part: u8 = positional
+        .first()
+        .expect(usage)
+        .parse()
+        .expect("Part is not a number");
+
+    // Without an answer the part is solved from the day's input
+    let answer = match positional.get(1) {
+        Some(answer) => answer.to_string(),
+        None => {
+            let day = solution(year, day);
+            let input = inputs::Lookup::from_args(args)
+                .read(day.year, day.day)
+                .unwrap_or_else(|err| panic!("{}", err));
+            let run = (day.solve)(&input);
+            let answer = if part == 1 {
+                run.part_one.0
+            } else {
+                run.part_two.0
+            };
+            answer.unwrap_or_else(|err| panic!("Part {} failed: {}", part, err))
+        }
+    };
+
+    println!(
+        "Submitting {} for {} day {} part {}",
+        answer, year, day, part
+    );
+    // Waits the site asks for are kept in the input store, so they hold across runs
+    let store = inputs::Lookup::from_args(args).root;
+    let result = client::Client::from_env(&store)
+        .and_then(|mut client| client.submit(year, day, part, &answer));
+    match result {
+        Ok(verdict) => println!("{}", verdict),
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn extract(args: &[String]) {
+    let usage = "Usage: aoc extract [year] <day> <page.html> [--fixtures=DIR]";
+    let (year, day, args) = target(args, usage);
+    let page = fs::read_to_string(args.first().expect(usage)).expect("Can't read page");
+    let root = fixtures_root(&args[1..]);
+
+    let examples = examples::extract(&page);
+    if examples.is_empty() {
+        println!("No examples with answers found");
+        process::exit(1);
+    }
+    for path in examples::write(&root, year, day, &examples).expect("Can't write fixtures") {
+        println!("Wrote {}", path.display());
+    }
+}
+
+fn fixtures_root(args: &[String]) -> PathBuf {
+    args.iter()
+        .find_map(|arg| arg.strip_prefix("--fixtures="))
+        .map_or_else(examples::default_root, PathBuf::from)
+}
+
+fn main() {
+    // Every day's trace module reads these for itself, commands never see them. Set in the
+    // environment too, so the runs aoc watch starts are traced the same way
+    let (trace, args): (Vec<String>, Vec<String>) = env::args()
+        .skip(1)
+        .partition(|arg| arg == "--verbose" || arg == "--trace");
+    if trace.iter().any(|arg| arg == "--trace") {
+        env::set_var("AOC_TRACE", "trace");
+    } else if !trace.is_empty() {
+        env::set_var("AOC_TRACE", "verbose");
+    }
+
+    match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("download") => download(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("extract") => extract(&args[1..]),
+        Some("examples") => {
+            let flag = |name: &str| {
+                args[1..]
+                    .iter()
+                    .find_map(|arg| arg.strip_prefix(name)?.strip_prefix('='))
+            };
+            let year = flag("--year").map(|year| year.parse().expect("Year is not a number"));
+            let day = flag("--day").map(|day| day.parse().expect("Day is not a number"));
+            if !examples::run(&fixtures_root(&args[1..]), year, day) {
+                process::exit(1);
+            }
+        }
+        Some("new") => match scaffold::new_day(&scaffold::Options::parse(&args[1..])) {
+            Ok(created) => {
+                for path in created {
+                    println!("Created {}", path.display());
+                }
+            }
+            Err(err) => {
+                println!("{}", err);
+                process::exit(1);
+            }
+        },
+        Some("bench") => {
+            if !bench::run(&bench::Options::parse(&args[1..])) {
+                process::exit(1);
+            }
+        }
+        Some("watch") => {
+            if let Err(err) = watch::run(&watch::Options::parse(&args[1..])) {
+                println!("{}", err);
+                process::exit(1);
+            }
+        }
+        Some("fuzz") => {
+            if !fuzz::run(&fuzz::Options::parse(&args[1..])) {
+                process::exit(1);
+            }
+        }
+        _ => {
+            println!("Usage: aoc run [year] <day> [input] [--name=NAME] [--inputs=DIR] [--json]");
+            println!(
+                "       aoc run --all [--year=N] [--jobs=N] [--name=NAME] [--inputs=DIR] [--json]"
+            );
+            println!("       aoc new [year] <day> [--template=DIR]");
+            println!("       aoc download [year] <day> [--name=NAME] [--inputs=DIR]");
+            println!("       aoc submit [year] <day> <part> [answer] [--name=NAME] [--inputs=DIR]");
+            println!("       aoc extract [year] <day> <page.html> [--fixtures=DIR]");
+            println!("       aoc examples [--year=N] [--day=N] [--fixtures=DIR]");
+            println!("       aoc generate [year] <day> [--size=N] [--seed=N]");
+            println!("       aoc watch [year] <day> [--name=NAME] [--inputs=DIR] [--input=PATH]");
+            println!("       aoc bench [--year=N] [--day=N] [--runs=N] [--input=PATH]... [--name=NAME] [--synthetic=SIZE] [--seed=N] [--save=FILE] [--baseline=FILE] [--threshold=PCT]");
+            println!(
+                "       aoc fuzz [--year=N] [--day=N] [--iterations=N] [--seed=N] [--crashes=DIR]"
+            );
+            println!("The year may be left out for the latest event");
+            println!("Any command takes --verbose or --trace to show what each day's parser and solver did");
+            process::exit(2);
+        }
+    }
+}
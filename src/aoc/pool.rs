@@ -0,0 +1,67 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+use parallel;
+
+// One worker per core unless --jobs says otherwise
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Unknown panic".to_string(),
+        },
+    }
+}
+
+// Runs the job over every item on up to `jobs` threads, which take the next item as they finish one.
+// Results keep the order of the items, and a job which panics gives its message without stopping the rest
+pub fn map<T, R, F>(items: &[T], jobs: usize, job: F) -> Vec<Result<R, String>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    parallel::map_with(items, jobs, |item| {
+        panic::catch_unwind(AssertUnwindSafe(|| job(item))).map_err(panic_message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<u64> = (0..50).collect();
+        // Early items take longest so later ones finish first
+        let squares = map(&items, 4, |n| {
+            thread::sleep(Duration::from_micros(50 - n));
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| Ok(n * n)).collect::<Vec<_>>());
+        assert_eq!(map(&items[..2], 0, |n| *n), vec![Ok(0), Ok(1)]);
+        assert_eq!(map(&[] as &[u64], 4, |n| *n), vec![]);
+    }
+
+    #[test]
+    fn test_map_isolates_panics() {
+        let results = map(&[1, 2, 3, 4], 2, |n| {
+            if *n == 2 {
+                panic!("No solution for {}", n);
+            }
+            n * 10
+        });
+        assert_eq!(
+            results,
+            vec![Ok(10), Err("No solution for 2".to_string()), Ok(30), Ok(40)]
+        );
+    }
+}
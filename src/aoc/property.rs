@@ -0,0 +1,340 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use generate::Rng;
+
+// A value which can suggest smaller versions of itself, to find the simplest input which fails
+pub trait Shrink: Clone + fmt::Debug {
+    fn shrink(&self) -> Vec<Self>;
+}
+
+impl Shrink for u64 {
+    fn shrink(&self) -> Vec<u64> {
+        let mut smaller = vec![0, self / 2, self.saturating_sub(1)];
+        smaller.dedup();
+        smaller.retain(|value| value < self);
+        smaller
+    }
+}
+
+impl Shrink for i64 {
+    fn shrink(&self) -> Vec<i64> {
+        let mut smaller = vec![0, self / 2, self - self.signum()];
+        smaller.dedup();
+        smaller.retain(|value| value.abs() < self.abs());
+        smaller
+    }
+}
+
+impl<T: Shrink> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Vec<T>> {
+        // Fewer items first, then smaller items
+        let mut smaller: Vec<Vec<T>> = (0..self.len())
+            .map(|index| {
+                let mut fewer = self.clone();
+                fewer.remove(index);
+                fewer
+            })
+            .collect();
+        for (index, item) in self.iter().enumerate() {
+            for item in item.shrink() {
+                let mut changed = self.clone();
+                changed[index] = item;
+                smaller.push(changed);
+            }
+        }
+        smaller
+    }
+}
+
+impl<A: Shrink, B: Shrink> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<(A, B)> {
+        let mut smaller: Vec<(A, B)> = self
+            .0
+            .shrink()
+            .into_iter()
+            .map(|a| (a, self.1.clone()))
+            .collect();
+        smaller.extend(self.1.shrink().into_iter().map(|b| (self.0.clone(), b)));
+        smaller
+    }
+}
+
+impl<A: Shrink, B: Shrink, C: Shrink> Shrink for (A, B, C) {
+    fn shrink(&self) -> Vec<(A, B, C)> {
+        ((self.0.clone(), self.1.clone()), self.2.clone())
+            .shrink()
+            .into_iter()
+            .map(|((a, b), c)| (a, b, c))
+            .collect()
+    }
+}
+
+impl<A: Shrink, B: Shrink, C: Shrink, D: Shrink> Shrink for (A, B, C, D) {
+    fn shrink(&self) -> Vec<(A, B, C, D)> {
+        (
+            (self.0.clone(), self.1.clone(), self.2.clone()),
+            self.3.clone(),
+        )
+            .shrink()
+            .into_iter()
+            .map(|((a, b, c), d)| (a, b, c, d))
+            .collect()
+    }
+}
+
+impl<T: Shrink> Shrink for (T,) {
+    fn shrink(&self) -> Vec<(T,)> {
+        self.0.shrink().into_iter().map(|value| (value,)).collect()
+    }
+}
+
+// A property which panics fails just like one which returns false
+fn holds<T>(property: &dyn Fn(&T) -> bool, input: &T) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(|| property(input))).unwrap_or(false)
+}
+
+// The smallest failing input over the seeds, or None if the property held for every case
+pub fn find_failure<T: Shrink>(
+    cases: u64,
+    generate: &dyn Fn(&mut Rng) -> T,
+    property: &dyn Fn(&T) -> bool,
+) -> Option<(u64, T)> {
+    let seed = (0..cases).find(|seed| !holds(property, &generate(&mut Rng::new(*seed))))?;
+
+    // Keep taking the first smaller input which still fails, until none do
+    let mut failing = generate(&mut Rng::new(seed));
+    while let Some(smaller) = failing
+        .shrink()
+        .into_iter()
+        .find(|candidate| !holds(property, candidate))
+    {
+        failing = smaller;
+    }
+    Some((seed, failing))
+}
+
+pub fn check<T: Shrink>(
+    cases: u64,
+    generate: &dyn Fn(&mut Rng) -> T,
+    property: &dyn Fn(&T) -> bool,
+) {
+    if let Some((seed, smallest)) = find_failure(cases, generate, property) {
+        panic!(
+            "Property failed for seed {}, smallest failing input: {:?}",
+            seed, smallest
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, find_failure, Shrink};
+    use generate::{Network, Rng};
+    use y2023::{day_5, day_6, day_7, day_8, day_9};
+
+    #[test]
+    fn test_shrinking() {
+        // Every list of 5 or more numbers fails, the simplest is five zeros
+        let failure = find_failure(
+            100,
+            &|rng| {
+                (0..rng.below(10))
+                    .map(|_| rng.below(1000))
+                    .collect::<Vec<u64>>()
+            },
+            &|numbers| numbers.len() < 5,
+        );
+        assert_eq!(failure.map(|(_, smallest)| smallest), Some(vec![0; 5]));
+
+        assert_eq!(
+            find_failure(100, &|rng| (rng.below(1000),), &|_| true).map(|(_, smallest)| smallest),
+            None
+        );
+        assert_eq!((-7_i64).shrink(), vec![0, -3, -6]);
+    }
+
+    // Seed ranges as (start, length), and the maps of each layer as (dest, source, range)
+    type AlmanacInput = (Vec<(u64, u64)>, Vec<Vec<(u64, u64, u64)>>);
+
+    fn almanac(input: &AlmanacInput) -> String {
+        let seeds: Vec<String> = input
+            .0
+            .iter()
+            .map(|(start, length)| format!("{} {}", start, length))
+            .collect();
+        let mut text = format!("seeds: {}\n", seeds.join(" "));
+        for layer in 0..7 {
+            text += "\nlayer map:\n";
+            for (dest, source, range) in input.1.get(layer).into_iter().flatten() {
+                text += &format!("{} {} {}\n", dest, source, range);
+            }
+        }
+        text
+    }
+
+    #[test]
+    fn test_day_5_seed_ranges() {
+        check(
+            300,
+            &|rng| {
+                let seeds = (0..rng.range(1, 3))
+                    .map(|_| (rng.below(100), rng.range(1, 20)))
+                    .collect::<Vec<(u64, u64)>>();
+                let layers = (0..7)
+                    .map(|_| {
+                        (0..rng.below(5))
+                            .map(|_| (rng.below(100), rng.below(100), rng.below(30)))
+                            .collect::<Vec<(u64, u64, u64)>>()
+                    })
+                    .collect::<Vec<Vec<(u64, u64, u64)>>>();
+                (seeds, layers)
+            },
+            &|input| {
+                // Shrinking may leave no seeds or an empty range, which the parser rejects
+                if input.0.is_empty() || input.0.iter().any(|(_, length)| *length == 0) {
+                    return true;
+                }
+                let almanac = day_5::parse(&almanac(input)).unwrap();
+                day_5::part_two(&almanac) == day_5::min_seed_range_loction(&almanac)
+            },
+        );
+    }
+
+    // Every race as (time, distance, acceleration, penalty)
+    type Race = (u64, u64, u64, u64);
+
+    fn races(races: &[Race]) -> String {
+        let line = |label: &str, value: &dyn Fn(&Race) -> u64| {
+            let values: Vec<String> = races.iter().map(|race| value(race).to_string()).collect();
+            format!("{}: {}\n", label, values.join(" "))
+        };
+        line("Time", &|race| race.0)
+            + &line("Distance", &|race| race.1)
+            + &line("Acceleration", &|race| race.2)
+            + &line("Penalty", &|race| race.3)
+    }
+
+    #[test]
+    fn test_day_6_winning_interval() {
+        check(
+            300,
+            &|rng| {
+                (0..rng.range(1, 3))
+                    .map(|_| (rng.below(60), rng.below(400), rng.range(1, 3), rng.below(6)))
+                    .collect::<Vec<(u64, u64, u64, u64)>>()
+            },
+            &|input| {
+                // A kerned race needs at least one number
+                if input.is_empty() {
+                    return true;
+                }
+                let races = day_6::parse(&races(input)).unwrap();
+                day_6::part_one(&races) == day_6::part_one_brute_force(&races)
+                    && day_6::part_two(&races) == day_6::part_two_brute_force(&races)
+            },
+        );
+    }
+
+    // Hands as indexes into the joker deck, with extra jokers since those are what rank has to get right
+    #[test]
+    fn test_day_7_joker_rank() {
+        check(
+            300,
+            &|rng| {
+                (0..rng.range(1, 6))
+                    .map(|_| {
+                        let cards = (0..5)
+                            .map(|_| if rng.chance(30) { 0 } else { rng.below(13) })
+                            .collect::<Vec<u64>>();
+                        (cards, rng.range(1, 1000))
+                    })
+                    .collect::<Vec<(Vec<u64>, u64)>>()
+            },
+            &|input| {
+                let text: String = input
+                    .iter()
+                    .map(|(cards, bid)| {
+                        let hand: String = cards
+                            .iter()
+                            .map(|card| b"J23456789TQKA"[*card as usize % 13] as char)
+                            .collect();
+                        format!("{} {}\n", hand, bid)
+                    })
+                    .collect();
+                let games = day_7::parse(&text).unwrap();
+                day_7::part_two(&games) == day_7::part_two_brute_force(&games)
+            },
+        );
+    }
+
+    // Networks built like the real puzzle, where the least common multiple is the right answer
+    #[test]
+    fn test_day_8_ghost_steps() {
+        check(
+            100,
+            &|rng| (rng.below(8), rng.range(1, 3), rng.below(8), rng.next_u64()),
+            &|(directions, ghosts, min_cycle, seed)| {
+                let network = Network {
+                    directions: directions % 8,
+                    ghosts: 1 + *ghosts as usize % 3,
+                    min_cycle: min_cycle % 8,
+                };
+                let map = day_8::parse(&network.generate(&mut Rng::new(*seed))).unwrap();
+                day_8::part_two(&map) == day_8::part_two_brute_force(&map)
+            },
+        );
+    }
+
+    // n choose k for any whole n, so the history can be read before the first value
+    fn choose(n: i64, k: usize) -> i64 {
+        (0..k as i64).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    // Each line is the first value of every row of differences, the value at x is the sum of
+    // first[k] * (x choose k), which predicts both directions without any difference table
+    #[test]
+    fn test_day_9_polynomials() {
+        check(
+            300,
+            &|rng| {
+                let lines = (0..rng.range(1, 4))
+                    .map(|_| {
+                        (0..rng.range(1, 6))
+                            .map(|_| rng.range(0, 20) as i64 - 10)
+                            .collect::<Vec<i64>>()
+                    })
+                    .collect::<Vec<Vec<i64>>>();
+                (lines, rng.below(5))
+            },
+            &|(lines, extra)| {
+                let lines: Vec<&Vec<i64>> =
+                    lines.iter().filter(|firsts| !firsts.is_empty()).collect();
+                let value = |firsts: &[i64], x: i64| {
+                    firsts
+                        .iter()
+                        .enumerate()
+                        .map(|(k, first)| first * choose(x, k))
+                        .sum::<i64>()
+                };
+
+                let mut text = String::new();
+                let mut next = 0;
+                let mut previous = 0;
+                for firsts in lines.iter() {
+                    // Enough values for the differences to reach a row of zeros
+                    let count = (firsts.len() as u64 + 1 + extra) as i64;
+                    let values: Vec<String> =
+                        (0..count).map(|x| value(firsts, x).to_string()).collect();
+                    text += &values.join(" ");
+                    text.push('\n');
+                    next += value(firsts, count);
+                    previous += value(firsts, -1);
+                }
+
+                let report = day_9::parse(&text).unwrap();
+                day_9::part_one(&report) == Ok(next) && day_9::part_two(&report) == Ok(previous)
+            },
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::fmt;
+use std::time::Duration;
+
+use days::{Answer, Day, Run};
+use inputs::Lookup;
+
+// One part of one run, written as a line of JSON for dashboards and scripts
+#[derive(Debug, PartialEq, Clone)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+    // Where the input was read from
+    pub input: String,
+}
+
+// Both parts share the parse, which is repeated in each record
+pub fn records(day: &Day, run: &Run, input: &str) -> Vec<Record> {
+    vec![(1, &run.part_one), (2, &run.part_two)]
+        .into_iter()
+        .map(|(part, (answer, solve))| Record {
+            year: day.year,
+            day: day.day,
+            part,
+            answer: answer.clone(),
+            parse: run.parse,
+            solve: *solve,
+            input: input.to_string(),
+        })
+        .collect()
+}
+
+// A JSON string, quotes included
+fn string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+// The text of a field in a record, a string still in its quotes
+fn field<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let key = format!("\"{}\":", name);
+    let rest = &line[line.find(&key)? + key.len()..];
+    let end = if rest.starts_with('"') {
+        // Up to the first quote which isn't escaped
+        let mut escaped = false;
+        let (end, _) = rest.char_indices().skip(1).find(|(_, c)| {
+            let end = *c == '"' && !escaped;
+            escaped = *c == '\\' && !escaped;
+            end
+        })?;
+        end + 1
+    } else {
+        rest.find([',', '}'])?
+    };
+    Some(&rest[..end])
+}
+
+// The text of a JSON string written by string
+fn unquote(quoted: &str) -> Option<String> {
+    let mut chars = quoted.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut text = String::new();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        text.push(match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let code: String = chars.by_ref().take(4).collect();
+                char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+            }
+            c => c,
+        });
+    }
+    Some(text)
+}
+
+impl Record {
+    // One line written by Display, such as the output of aoc run --json
+    pub fn parse(line: &str) -> Option<Record> {
+        let millis = |name: &str| -> Option<Duration> {
+            let millis: f64 = field(line, name)?.parse().ok()?;
+            Some(Duration::from_nanos((millis * 1_000_000.0).round() as u64))
+        };
+        let answer = match field(line, "answer")? {
+            "null" => Err(unquote(field(line, "error")?)?),
+            answer => Ok(unquote(answer)?),
+        };
+        Some(Record {
+            year: field(line, "year")?.parse().ok()?,
+            day: field(line, "day")?.parse().ok()?,
+            part: field(line, "part")?.parse().ok()?,
+            answer,
+            parse: millis("parse_ms")?,
+            solve: millis("solve_ms")?,
+            input: unquote(field(line, "input")?)?,
+        })
+    }
+}
+
+// Answers are strings since some don't fit a JSON number, a failed part has a null answer and an error
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answer = match &self.answer {
+            Ok(answer) => string(answer),
+            Err(err) => format!("null,\"error\":{}", string(err)),
+        };
+        write!(
+            f,
+            "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"parse_ms\":{},\"solve_ms\":{},\"input\":{}}}",
+            self.year,
+            self.day,
+            self.part,
+            answer,
+            millis(self.parse),
+            millis(self.solve),
+            string(&self.input)
+        )
+    }
+}
+
+// What came of one day when every day is run at once
+#[derive(Debug)]
+pub enum Outcome {
+    Solved { input: String, run: Run },
+    Missing(String),
+    Panicked(String),
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        match self {
+            Outcome::Solved { run, .. } => run.part_one.0.is_ok() && run.part_two.0.is_ok(),
+            _ => false,
+        }
+    }
+
+    // Every part's record, a day which never ran has the reason as both parts' error
+    pub fn records(&self, day: &Day) -> Vec<Record> {
+        let err = match self {
+            Outcome::Solved { input, run } => return records(day, run, input),
+            Outcome::Missing(err) | Outcome::Panicked(err) => err,
+        };
+        (1..=2)
+            .map(|part| Record {
+                year: day.year,
+                day: day.day,
+                part,
+                answer: Err(err.clone()),
+                parse: Duration::ZERO,
+                solve: Duration::ZERO,
+                input: String::new(),
+            })
+            .collect()
+    }
+}
+
+pub fn solve(day: &Day, lookup: &Lookup) -> Outcome {
+    match lookup.open(day.year, day.day) {
+        Ok((path, input)) => Outcome::Solved {
+            input: path.to_string_lossy().into_owned(),
+            run: (day.solve)(&input),
+        },
+        Err(err) => Outcome::Missing(err.to_string()),
+    }
+}
+
+fn cell(answer: &Answer) -> String {
+    match answer {
+        Ok(answer) => answer.clone(),
+        Err(err) => format!("failed: {}", err),
+    }
+}
+
+pub fn header() -> String {
+    format!(
+        "{:>4} {:>3}  {:<20} {:<20} {:>12} {:>12} {:>12}",
+        "Year", "Day", "Part 1", "Part 2", "Parse", "Part 1", "Part 2"
+    )
+}
+
+// One line of the summary table under the header
+pub fn row(day: &Day, outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Solved { run, .. } => format!(
+            "{:>4} {:>3}  {:<20} {:<20} {:>12} {:>12} {:>12}",
+            day.year,
+            day.day,
+            cell(&run.part_one.0),
+            cell(&run.part_two.0),
+            format!("{:?}", run.parse),
+            format!("{:?}", run.part_one.1),
+            format!("{:?}", run.part_two.1)
+        ),
+        Outcome::Missing(err) => format!("{:>4} {:>3}  {}", day.year, day.day, err),
+        Outcome::Panicked(message) => {
+            format!("{:>4} {:>3}  panicked: {}", day.year, day.day, message)
+        }
+    }
+}
+
+// Time spent parsing and solving, which is more than the time taken when days run side by side
+pub fn busy(outcomes: &[Outcome]) -> Duration {
+    outcomes
+        .iter()
+        .map(|outcome| match outcome {
+            Outcome::Solved { run, .. } => run.parse + run.part_one.1 + run.part_two.1,
+            _ => Duration::ZERO,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{records, row, string, Outcome, Record};
+    use days;
+    use std::time::Duration;
+
+    #[test]
+    fn test_string() {
+        assert_eq!(string("plain"), "\"plain\"");
+        assert_eq!(
+            string("a \"b\"\\c\nd\u{1}"),
+            "\"a \\\"b\\\"\\\\c\\nd\\u0001\""
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let mut record = Record {
+            year: 2023,
+            day: 5,
+            part: 1,
+            answer: Ok("35".to_string()),
+            parse: Duration::from_micros(1500),
+            solve: Duration::from_nanos(20),
+            input: "inputs/2023/5/input.txt".to_string(),
+        };
+        assert_eq!(
+            record.to_string(),
+            r#"{"year":2023,"day":5,"part":1,"answer":"35","parse_ms":1.500,"solve_ms":0.000,"input":"inputs/2023/5/input.txt"}"#
+        );
+
+        record.answer = Err("Line 1: \"x\" is not a number".to_string());
+        assert!(record
+            .to_string()
+            .contains(r#""answer":null,"error":"Line 1: \"x\" is not a number","#));
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut record = Record {
+            year: 2023,
+            day: 8,
+            part: 2,
+            answer: Ok("6".to_string()),
+            parse: Duration::from_micros(1500),
+            solve: Duration::from_millis(12),
+            input: "C:\\inputs\\2023\\8\\input.txt".to_string(),
+        };
+        assert_eq!(Record::parse(&record.to_string()), Some(record.clone()));
+
+        record.answer = Err("Node \"AAA\" is missing,\n\"answer\":\"1\"}\u{1}".to_string());
+        assert_eq!(Record::parse(&record.to_string()), Some(record));
+        assert_eq!(Record::parse("Input missing for 2023 day 8"), None);
+    }
+
+    #[test]
+    fn test_records() {
+        let day = days::find(2023, 6).unwrap();
+        let run = (day.solve)("Time:      7  15   30\nDistance:  9  40  200\n");
+        let found = records(day, &run, "example");
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            (found[0].part, &found[0].answer),
+            (1, &Ok("288".to_string()))
+        );
+        assert_eq!(
+            (found[1].part, &found[1].answer),
+            (2, &Ok("71503".to_string()))
+        );
+        assert_eq!(found[1].parse, run.parse);
+        assert_eq!(found[1].solve, run.part_two.1);
+    }
+
+    #[test]
+    fn test_outcome() {
+        let day = days::find(2023, 6).unwrap();
+        let solved = Outcome::Solved {
+            input: "example".to_string(),
+            run: (day.solve)("Time:      7  15   30\nDistance:  9  40  200\n"),
+        };
+        assert!(solved.is_ok());
+        assert!(row(day, &solved).starts_with("2023   6  288                  71503 "));
+
+        let broken = Outcome::Solved {
+            input: "example".to_string(),
+            run: (day.solve)("Time: 7\n"),
+        };
+        assert!(!broken.is_ok());
+        assert!(row(day, &broken).contains("failed: "));
+
+        let panicked = Outcome::Panicked("attempt to add with overflow".to_string());
+        assert!(!panicked.is_ok());
+        assert_eq!(
+            row(day, &panicked),
+            "2023   6  panicked: attempt to add with overflow"
+        );
+        let found = panicked.records(day);
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            found[1].answer,
+            Err("attempt to add with overflow".to_string())
+        );
+    }
+}
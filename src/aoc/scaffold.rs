@@ -0,0 +1,436 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use days;
+use inputs::{Lookup, DEFAULT_NAME};
+
+// Markers in each year's registry which new days are added above
+const MODULE_MARKER: &str = "// aoc new adds modules above this line";
+const DAY_MARKER: &str = "    // aoc new adds days above this line";
+// Markers in main.rs and days.rs which new years are added above
+const YEAR_MODULE_MARKER: &str = "// aoc new adds years above this line";
+const YEAR_MARKER: &str = "    // aoc new adds years above this line";
+
+pub struct Options {
+    pub year: u16,
+    pub day: u8,
+    // Every file in the template is copied into the day, with {{year}} and {{day}} filled in
+    pub template: PathBuf,
+    // Where the <year>/day-N directories live
+    pub src: PathBuf,
+    // Where main.rs, days.rs and each year's y<year>.rs registry live
+    pub runner: PathBuf,
+    pub fixtures: PathBuf,
+    pub inputs: PathBuf,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Options {
+        let usage = "Usage: aoc new [year] <day> [--template=DIR]";
+        // A new day belongs to the latest year unless another is given
+        let (year, day, args) = days::split_target(args).expect(usage);
+        let mut options = Options {
+            year: year.unwrap_or_else(days::latest_year),
+            day,
+            template: PathBuf::from("template"),
+            src: PathBuf::from(".."),
+            runner: PathBuf::from("."),
+            fixtures: PathBuf::from("fixtures"),
+            inputs: Lookup::from_env().root,
+        };
+
+        for arg in args.iter() {
+            match arg.split_once('=') {
+                Some(("--template", value)) => options.template = PathBuf::from(value),
+                _ => panic!("Unknown argument {}", arg),
+            }
+        }
+        options
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    AlreadyRegistered(u16, u8),
+    MissingMarker(&'static str),
+    Io(String),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::AlreadyRegistered(year, day) => {
+                write!(
+                    f,
+                    "{} day {} is already registered with the runner",
+                    year, day
+                )
+            }
+            ScaffoldError::MissingMarker(marker) => {
+                write!(f, "Runner is missing the line '{}'", marker.trim())
+            }
+            ScaffoldError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+fn io_error(err: std::io::Error) -> ScaffoldError {
+    ScaffoldError::Io(err.to_string())
+}
+
+pub fn render(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+}
+
+// A registry for a year with no days yet
+pub fn year_registry() -> String {
+    format!(
+        "use days::Day;\n\n{}\n\npub static DAYS: &[Day] = &[\n{}\n];\n",
+        MODULE_MARKER, DAY_MARKER
+    )
+}
+
+fn marker(lines: &[String], marker: &'static str) -> Result<usize, ScaffoldError> {
+    lines
+        .iter()
+        .position(|line| line == marker)
+        .ok_or(ScaffoldError::MissingMarker(marker))
+}
+
+// The day a registry line declares a module or an entry for
+fn registered_day(line: &str) -> Option<u8> {
+    if let Some(module) = line.strip_prefix("pub mod day_") {
+        return module.strip_suffix(';')?.parse().ok();
+    }
+    let entry = line.trim_start().strip_prefix("day!(")?;
+    entry.split(',').nth(1)?.trim().parse().ok()
+}
+
+// The year's registry with the day's module and entry added, both in day order so day_10 follows
+// day_9 rather than day_1
+pub fn register(registry: &str, year: u16, day: u8) -> Result<String, ScaffoldError> {
+    let name = format!("day_{}", day);
+    let module = format!("pub mod {};", name);
+    if registry.lines().any(|line| line == module) {
+        return Err(ScaffoldError::AlreadyRegistered(year, day));
+    }
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+    let (module_marker, day_marker) = (marker(&lines, MODULE_MARKER)?, marker(&lines, DAY_MARKER)?);
+    let after = |lines: &[String]| {
+        lines
+            .iter()
+            .position(|line| registered_day(line).is_some_and(|other| other > day))
+    };
+
+    // Entries follow the modules, so adding one first leaves the module lines where they were
+    let at =
+        after(&lines[module_marker..day_marker]).map_or(day_marker, |index| module_marker + index);
+    lines.insert(at, format!("    day!({}, {}, {}),", year, day, name));
+    // Above the attributes of the first module for a later day
+    let at = match after(&lines[..module_marker]) {
+        Some(index) => (0..index)
+            .rev()
+            .take_while(|above| lines[*above].starts_with("#["))
+            .last()
+            .unwrap_or(index),
+        None => module_marker,
+    };
+    lines.splice(
+        at..at,
+        [
+            "#[allow(dead_code)]".to_string(),
+            format!("#[path = \"../{}/day-{}/main.rs\"]", year, day),
+            module,
+        ],
+    );
+    Ok(lines.join("\n") + "\n")
+}
+
+// The source with the line added above the marker, after any lines like it which sort before it
+fn insert_sorted(
+    source: &str,
+    marker_line: &'static str,
+    line: String,
+) -> Result<String, ScaffoldError> {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    let end = marker(&lines, marker_line)?;
+    let indent = marker_line.len() - marker_line.trim_start().len();
+    let at = (0..end)
+        .rev()
+        .take_while(|index| {
+            let other = &lines[*index];
+            other.len() - other.trim_start().len() == indent && other.trim() > line.trim()
+        })
+        .last()
+        .unwrap_or(end);
+    lines.insert(at, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+// main.rs and days.rs with the year's registry declared and added to days::YEARS
+pub fn register_year(main: &str, days: &str, year: u16) -> Result<(String, String), ScaffoldError> {
+    Ok((
+        insert_sorted(main, YEAR_MODULE_MARKER, format!("mod y{};", year))?,
+        insert_sorted(days, YEAR_MARKER, format!("    ::y{}::DAYS,", year))?,
+    ))
+}
+
+fn create(path: &Path, contents: &str) -> Result<PathBuf, ScaffoldError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, contents).map_err(io_error)?;
+    Ok(path.to_path_buf())
+}
+
+// Creates the day from the template, registers it and leaves empty fixtures and an input to fill in.
+// The first day of a year also creates the year's registry and adds it to the runner
+pub fn new_day(options: &Options) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let (year, day) = (options.year, options.day);
+    let dir = options
+        .src
+        .join(year.to_string())
+        .join(format!("day-{}", day));
+    if dir.exists() {
+        return Err(ScaffoldError::Exists(dir));
+    }
+
+    // Everything the runner needs is checked before any file is written
+    let registry_path = options.runner.join(format!("y{}.rs", year));
+    let (registry, runner) = if registry_path.exists() {
+        (fs::read_to_string(&registry_path).map_err(io_error)?, None)
+    } else {
+        let main = options.runner.join("main.rs");
+        let days = options.runner.join("days.rs");
+        let (main_source, days_source) = register_year(
+            &fs::read_to_string(&main).map_err(io_error)?,
+            &fs::read_to_string(&days).map_err(io_error)?,
+            year,
+        )?;
+        (
+            year_registry(),
+            Some([(main, main_source), (days, days_source)]),
+        )
+    };
+    let registry = register(&registry, year, day)?;
+
+    let mut created = Vec::new();
+    let mut templates: Vec<PathBuf> = fs::read_dir(&options.template)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()).map_err(io_error))
+        .collect::<Result<Vec<PathBuf>, ScaffoldError>>()?;
+    templates.sort();
+    for template in templates {
+        let text = fs::read_to_string(&template).map_err(io_error)?;
+        let name = template.file_name().unwrap();
+        created.push(create(&dir.join(name), &render(&text, year, day))?);
+    }
+
+    // Empty answers are skipped until aoc extract or a person fills them in
+    let fixtures = options
+        .fixtures
+        .join(year.to_string())
+        .join(day.to_string());
+    for part in 1..=2 {
+        for extension in ["txt", "answer"] {
+            let path = fixtures.join(format!("part-{}.{}", part, extension));
+            if !path.exists() {
+                created.push(create(&path, "")?);
+            }
+        }
+    }
+
+    // An empty input reads as missing, so the day still says where to save it
+    let input = Lookup {
+        root: options.inputs.clone(),
+        name: None,
+        file: None,
+    }
+    .path(year, day, DEFAULT_NAME);
+    if !input.exists() {
+        created.push(create(&input, "")?);
+    }
+
+    fs::write(&registry_path, registry).map_err(io_error)?;
+    created.push(registry_path);
+    for (path, source) in runner.into_iter().flatten() {
+        fs::write(&path, source).map_err(io_error)?;
+        created.push(path);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new_day, register, register_year, render, year_registry, Options, ScaffoldError};
+    use super::{DAY_MARKER, YEAR_MARKER};
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    static REGISTRY: &str = "\
+use days::Day;
+
+#[allow(dead_code)]
+#[path = \"../2023/day-1/main.rs\"]
+pub mod day_1;
+// aoc new adds modules above this line
+
+pub static DAYS: &[Day] = &[
+    day!(2023, 1, day_1, Calibration),
+    // aoc new adds days above this line
+];
+";
+
+    static MAIN: &str = "\
+mod scaffold;
+// Each event's days, registered in days::YEARS
+mod y2023;
+// aoc new adds years above this line
+";
+
+    static DAYS: &str = "\
+pub static YEARS: &[&[Day]] = &[
+    ::y2023::DAYS,
+    // aoc new adds years above this line
+];
+";
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render("inputs/{{year}}/{{day}}, Day {{day}}", 2023, 10),
+            "inputs/2023/10, Day 10"
+        );
+    }
+
+    #[test]
+    fn test_register() {
+        let registry = register(REGISTRY, 2023, 2).unwrap();
+        assert_eq!(
+            registry,
+            REGISTRY
+                .replace(
+                    "// aoc new adds modules",
+                    "#[allow(dead_code)]\n#[path = \"../2023/day-2/main.rs\"]\npub mod day_2;\n// aoc new adds modules"
+                )
+                .replace(DAY_MARKER, "    day!(2023, 2, day_2),\n    // aoc new adds days above this line")
+        );
+
+        // day_10 goes after day_2, and day_3 between them
+        let registry = register(&registry, 2023, 10).unwrap();
+        assert!(registry.contains("pub mod day_2;\n#[allow(dead_code)]\n#[path = \"../2023/day-10/main.rs\"]\npub mod day_10;\n// aoc new adds modules"));
+        assert!(registry.contains("day!(2023, 2, day_2),\n    day!(2023, 10, day_10),"));
+        let registry = register(&registry, 2023, 3).unwrap();
+        assert!(registry.contains("pub mod day_2;\n#[allow(dead_code)]\n#[path = \"../2023/day-3/main.rs\"]\npub mod day_3;\n#[allow(dead_code)]\n#[path = \"../2023/day-10/main.rs\"]"));
+        assert!(registry.contains(
+            "day!(2023, 2, day_2),\n    day!(2023, 3, day_3),\n    day!(2023, 10, day_10),"
+        ));
+        assert_eq!(
+            register(&registry, 2023, 10),
+            Err(ScaffoldError::AlreadyRegistered(2023, 10))
+        );
+        assert_eq!(
+            register("pub mod day_1;", 2023, 2),
+            Err(ScaffoldError::MissingMarker(
+                "// aoc new adds modules above this line"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_register_after_day_9() {
+        let registry = (2..10).fold(REGISTRY.to_string(), |registry, day| {
+            register(&registry, 2023, day).unwrap()
+        });
+        let registry = register(&registry, 2023, 10).unwrap();
+        let modules: Vec<&str> = registry
+            .lines()
+            .filter(|line| line.starts_with("pub mod"))
+            .collect();
+        assert_eq!(modules.len(), 10);
+        assert_eq!(modules[8..], ["pub mod day_9;", "pub mod day_10;"]);
+        assert!(registry.contains("pub mod day_9;\n#[allow(dead_code)]\n#[path = \"../2023/day-10/main.rs\"]\npub mod day_10;\n"));
+        assert!(registry.contains(
+            "day!(2023, 9, day_9),\n    day!(2023, 10, day_10),\n    // aoc new adds days"
+        ));
+    }
+
+    #[test]
+    fn test_register_year() {
+        let registry = register(&year_registry(), 2024, 1).unwrap();
+        assert!(registry.starts_with("use days::Day;\n\n#[allow(dead_code)]\n#[path = \"../2024/day-1/main.rs\"]\npub mod day_1;\n"));
+        assert!(registry.ends_with(
+            "    day!(2024, 1, day_1),\n    // aoc new adds days above this line\n];\n"
+        ));
+
+        let (main, days) = register_year(MAIN, DAYS, 2024).unwrap();
+        assert!(main.contains("mod y2023;\nmod y2024;\n// aoc new adds years"));
+        assert!(days.contains("::y2023::DAYS,\n    ::y2024::DAYS,\n    // aoc new adds years"));
+
+        // An older event still goes where rustfmt and days::YEARS expect it
+        let (main, days) = register_year(&main, &days, 2022).unwrap();
+        assert!(main.contains("days::YEARS\nmod y2022;\nmod y2023;\nmod y2024;\n"));
+        assert!(days.contains("= &[\n    ::y2022::DAYS,\n    ::y2023::DAYS,"));
+        assert_eq!(
+            register_year(MAIN, "", 2024),
+            Err(ScaffoldError::MissingMarker(YEAR_MARKER))
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        fs::create_dir_all(root.join("template")).unwrap();
+        fs::write(root.join("template/main.rs"), "// {{year}} day {{day}}\n").unwrap();
+        fs::write(root.join("y2023.rs"), REGISTRY).unwrap();
+        fs::write(root.join("main.rs"), MAIN).unwrap();
+        fs::write(root.join("days.rs"), DAYS).unwrap();
+        let mut options = Options {
+            year: 2023,
+            day: 10,
+            template: root.join("template"),
+            src: root.clone(),
+            runner: root.clone(),
+            fixtures: root.join("fixtures"),
+            inputs: root.join("inputs"),
+        };
+
+        let created = new_day(&options).unwrap();
+        assert_eq!(created.len(), 7);
+        assert_eq!(
+            fs::read_to_string(root.join("2023/day-10/main.rs")).unwrap(),
+            "// 2023 day 10\n"
+        );
+        assert!(root.join("fixtures/2023/10/part-2.answer").is_file());
+        assert!(root.join("inputs/2023/10/input.txt").is_file());
+        assert!(fs::read_to_string(root.join("y2023.rs"))
+            .unwrap()
+            .contains("day!(2023, 10, day_10),"));
+        assert_eq!(
+            new_day(&options),
+            Err(ScaffoldError::Exists(root.join("2023/day-10")))
+        );
+
+        // The same day of a new year has a registry of its own
+        options.year = 2024;
+        let created = new_day(&options).unwrap();
+        assert_eq!(created.len(), 9);
+        assert!(root.join("2024/day-10/main.rs").is_file());
+        assert!(fs::read_to_string(root.join("y2024.rs"))
+            .unwrap()
+            .contains("day!(2024, 10, day_10),"));
+        assert!(fs::read_to_string(root.join("main.rs"))
+            .unwrap()
+            .contains("mod y2024;"));
+        assert!(fs::read_to_string(root.join("days.rs"))
+            .unwrap()
+            .contains("::y2024::DAYS,"));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
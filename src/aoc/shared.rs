@@ -0,0 +1,143 @@
+// Tests for the files shared with the day binaries, kept here so they run once rather than in every
+// day that includes them
+use std::env;
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use inputs::{InputError, Lookup};
+use parallel;
+use pool;
+use trace::{self, Level};
+
+// A store of its own for every test
+fn store(test: &str) -> PathBuf {
+    let root = env::temp_dir().join(format!("aoc-inputs-{}-{}", test, process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("2023").join("5")).unwrap();
+    fs::write(root.join("2023/5/input.txt"), "mine").unwrap();
+    fs::write(root.join("2023/5/alice.txt"), "alice's").unwrap();
+    root
+}
+
+fn lookup(root: &Path, name: Option<&str>) -> Lookup {
+    Lookup {
+        root: root.to_path_buf(),
+        name: name.map(String::from),
+        file: None,
+    }
+}
+
+#[test]
+fn test_inputs_read() {
+    let root = store("read");
+
+    assert_eq!(lookup(&root, None).read(2023, 5), Ok("mine".to_string()));
+    assert_eq!(
+        lookup(&root, Some("alice")).read(2023, 5),
+        Ok("alice's".to_string())
+    );
+    assert_eq!(
+        lookup(&root, None).names(2023, 5),
+        vec!["alice".to_string(), "input".to_string()]
+    );
+
+    let file = Lookup {
+        file: Some(root.join("2023/5/alice.txt")),
+        ..lookup(&root, Some("bob"))
+    };
+    assert_eq!(file.read(2023, 5), Ok("alice's".to_string()));
+    assert_eq!(
+        file.open(2023, 5),
+        Ok((root.join("2023/5/alice.txt"), "alice's".to_string()))
+    );
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_inputs_missing() {
+    let root = store("missing");
+
+    let err = lookup(&root, Some("bob")).read(2023, 5).unwrap_err();
+    assert_eq!(
+        err,
+        InputError::Missing {
+            year: 2023,
+            day: 5,
+            tried: vec![root.join("2023/5/bob.txt")],
+            available: vec!["alice".to_string(), "input".to_string()],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Input missing for 2023 day 5, looked for {} (saved inputs: alice, input)",
+            root.join("2023/5/bob.txt").display()
+        )
+    );
+    assert_eq!(lookup(&root, None).names(2023, 6), Vec::<String>::new());
+
+    fs::write(root.join("2023/5/bob.txt"), "").unwrap();
+    assert!(lookup(&root, Some("bob")).read(2023, 5).is_err());
+    assert_eq!(lookup(&root, None).names(2023, 5).len(), 2);
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_inputs_from_args() {
+    let args: Vec<String> = ["--brute-force", "--name=alice", "--inputs=/tmp/store"]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+    let lookup = Lookup::from_args(&args);
+
+    assert_eq!(lookup.root, PathBuf::from("/tmp/store"));
+    assert_eq!(lookup.name, Some("alice".to_string()));
+}
+
+#[test]
+fn test_parallel_map() {
+    let items: Vec<u64> = (0..1000).collect();
+    let squares: Vec<u64> = items.iter().map(|n| n * n).collect();
+    assert_eq!(parallel::map(&items, |n| n * n), squares);
+    assert_eq!(parallel::map_with(&items, 7, |n| n * n), squares);
+    assert_eq!(parallel::map_with(&items[..2], 0, |n| *n), vec![0, 1]);
+    assert_eq!(parallel::map(&[] as &[u64], |n| *n), Vec::<u64>::new());
+}
+
+#[test]
+fn test_parallel_map_panics() {
+    let result = panic::catch_unwind(|| {
+        parallel::map_with(&[1, 2, 3], 2, |n| {
+            if *n == 2 {
+                panic!("No solution for {}", n);
+            }
+            *n
+        })
+    });
+    assert_eq!(
+        pool::panic_message(result.unwrap_err()),
+        "No solution for 2"
+    );
+}
+
+#[test]
+fn test_trace_parse_level() {
+    let args = |args: &[&str]| -> Vec<String> { args.iter().map(|arg| arg.to_string()).collect() };
+    assert_eq!(trace::parse_level(&args(&["main.exe"]), None), Level::Off);
+    assert_eq!(
+        trace::parse_level(&args(&["aoc", "run", "5", "--verbose"]), None),
+        Level::Verbose
+    );
+    assert_eq!(
+        trace::parse_level(&args(&["--verbose", "--trace"]), Some("verbose")),
+        Level::Trace
+    );
+    assert_eq!(trace::parse_level(&args(&[]), Some("trace")), Level::Trace);
+    assert_eq!(
+        trace::parse_level(&args(&["--verbose"]), Some("trace")),
+        Level::Verbose
+    );
+    assert_eq!(trace::parse_level(&args(&[]), Some("loud")), Level::Off);
+}
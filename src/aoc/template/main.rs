@@ -0,0 +1,89 @@
+use std::fmt;
+
+#[path = "../../inputs.rs"]
+mod inputs;
+#[macro_use]
+#[path = "../../trace.rs"]
+mod trace;
+
+const YEAR: u16 = {{year}};
+const DAY: u8 = {{day}};
+
+fn main() {
+    println!("Hello Day {{day}}!\n");
+
+    let input = match inputs::load(YEAR, DAY) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+    let lines = match parse(&input) {
+        Ok(lines) => lines,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+
+    let answer = part_one(&lines);
+    println!("Part 1: {answer}");
+
+    let answer = part_two(&lines);
+    println!("Part 2: {answer}");
+}
+
+pub(crate) fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    let _span = span!("{} day {} parse", YEAR, DAY);
+    if input.trim().is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let lines: Vec<String> = input.lines().map(String::from).collect();
+    verbose!("{} lines", lines.len());
+    Ok(lines)
+}
+
+pub(crate) fn part_one(lines: &[String]) -> usize {
+    let _span = span!("{} day {} part one", YEAR, DAY);
+    lines.len()
+}
+
+pub(crate) fn part_two(lines: &[String]) -> usize {
+    let _span = span!("{} day {} part two", YEAR, DAY);
+    lines.len()
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParseError {
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "Input is empty"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from the puzzle, aoc extract writes the same into the fixtures
+    static TEXT: &str = "\
+";
+
+    #[test]
+    #[ignore]
+    fn test_part_one() {
+        assert_eq!(part_one(&parse(TEXT).unwrap()), 0);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_part_two() {
+        assert_eq!(part_two(&parse(TEXT).unwrap()), 0);
+    }
+}
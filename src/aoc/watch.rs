@@ -0,0 +1,495 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+
+use days::{self, Answer};
+use inputs::{self, Lookup, DEFAULT_NAME};
+use report::Record;
+
+pub struct Options {
+    pub year: u16,
+    pub day: u8,
+    // --name, --inputs and --input, passed on to every run
+    pub inputs: Vec<String>,
+}
+
+impl Options {
+    // The day is given as `aoc run` takes it, `[year] <day>`, or with --day and --year, each
+    // either as --day=N or --day N
+    pub fn parse(args: &[String]) -> Options {
+        let usage = "Usage: aoc watch [year] <day> [--name=NAME] [--inputs=DIR] [--input=PATH]";
+        let (mut year, mut day, rest) = match days::parse_target(args) {
+            Some((year, day, rest)) => (Some(year), Some(day), rest),
+            None => (None, None, args),
+        };
+        let mut inputs = Vec::new();
+        let mut rest = rest.iter();
+        while let Some(arg) = rest.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| rest.next().cloned())
+                    .unwrap_or_else(|| panic!("{} needs a value", flag))
+            };
+            match flag {
+                "--year" => year = Some(value().parse().expect("Year is not a number")),
+                "--day" => day = Some(value().parse().expect("Day is not a number")),
+                "--name" | "--inputs" | "--input" => inputs.push(format!("{}={}", flag, value())),
+                _ => panic!("Unknown argument {}", arg),
+            }
+        }
+        Options {
+            year: year.unwrap_or_else(days::latest_year),
+            day: day.expect(usage),
+            inputs,
+        }
+    }
+}
+
+// Each part's answer from one run
+pub type Answers = Vec<(u8, Answer)>;
+
+fn describe(part: u8, answer: &Answer) -> String {
+    match answer {
+        Ok(answer) => format!("Part {}: {}", part, answer),
+        Err(err) => format!("Part {} failed: {}", part, err),
+    }
+}
+
+// A line for each part, saying what it was on the last run
+pub fn diff(answers: &Answers, previous: Option<&Answers>) -> Vec<String> {
+    answers
+        .iter()
+        .map(|(part, answer)| {
+            let line = describe(*part, answer);
+            let before = previous
+                .and_then(|previous| previous.iter().find(|(other, _)| other == part))
+                .map(|(_, before)| before);
+            match before {
+                Some(before) if before == answer => format!("{} (unchanged)", line),
+                Some(Ok(before)) => format!("{} (was {})", line, before),
+                Some(Err(_)) => format!("{} (was failing)", line),
+                None => line,
+            }
+        })
+        .collect()
+}
+
+// Compiles a day or the runner into the build directory, giving the compiler's errors if it fails
+fn compile(source: &Path, output: &Path, test: bool) -> Result<(), String> {
+    let mut rustc = Command::new("rustc");
+    rustc.arg(source).arg("-o").arg(output);
+    if test {
+        rustc.arg("--test");
+    }
+    let compiled = rustc.output().map_err(|err| err.to_string())?;
+    if compiled.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&compiled.stderr).into_owned())
+    }
+}
+
+struct Paths {
+    day: PathBuf,
+    runner: PathBuf,
+    build: PathBuf,
+}
+
+// Tests the day, then solves it with a fresh runner. Returns no answers when a build fails or
+// nothing was solved, so the next run is still compared with the last answers seen
+fn cycle(options: &Options, paths: &Paths, previous: Option<&Answers>) -> Option<Answers> {
+    let tests = paths
+        .build
+        .join(format!("day-tests{}", env::consts::EXE_SUFFIX));
+    if let Err(errors) = compile(&paths.day.join("main.rs"), &tests, true) {
+        println!("{}", errors);
+        return None;
+    }
+    match Command::new(&tests).output() {
+        Ok(output) if output.status.success() => {
+            let output = String::from_utf8_lossy(&output.stdout);
+            let summary = output
+                .lines()
+                .rev()
+                .find(|line| line.starts_with("test result"));
+            println!("{}", summary.unwrap_or("Tests passed"));
+        }
+        // Failing tests are shown in full, but the parts still run
+        Ok(output) => println!("{}", String::from_utf8_lossy(&output.stdout)),
+        Err(err) => println!("Can't run the tests: {}", err),
+    }
+
+    let runner = paths.build.join(format!("aoc{}", env::consts::EXE_SUFFIX));
+    if let Err(errors) = compile(&paths.runner, &runner, false) {
+        println!("{}", errors);
+        return None;
+    }
+    let output = match Command::new(&runner)
+        .arg("run")
+        .arg(options.year.to_string())
+        .arg(options.day.to_string())
+        .arg("--json")
+        .args(&options.inputs)
+        // Traces from aoc watch --verbose or --trace, and any panic, are shown as they happen
+        .stderr(Stdio::inherit())
+        .output()
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).into_owned(),
+        Err(err) => {
+            println!("Can't run the runner: {}", err);
+            return None;
+        }
+    };
+    let answers: Answers = output
+        .lines()
+        .filter_map(Record::parse)
+        .map(|record| (record.part, record.answer))
+        .collect();
+    if answers.is_empty() {
+        // Such as a missing input
+        print!("{}", output);
+        return None;
+    }
+    for line in diff(&answers, previous) {
+        println!("{}", line);
+    }
+    Some(answers)
+}
+
+// Sources and inputs, not editor swap files or build output
+fn relevant(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "rs" || extension == "txt")
+}
+
+// Runs the day now and again whenever its sources or input change, until interrupted
+pub fn run(options: &Options) -> io::Result<()> {
+    let src = inputs::repo_root()
+        .map(|root| root.join("src"))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Not inside the repository"))?;
+    let paths = Paths {
+        day: src
+            .join(options.year.to_string())
+            .join(format!("day-{}", options.day)),
+        runner: src.join("aoc").join("main.rs"),
+        build: env::temp_dir().join(format!("aoc-watch-{}", process::id())),
+    };
+    if !paths.day.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No solution at {}", paths.day.display()),
+        ));
+    }
+
+    // The store's directory is made so an input saved into it later is seen
+    let lookup = Lookup::from_args(&options.inputs);
+    let input = match lookup.file.as_ref() {
+        Some(file) => file.clone(),
+        None => lookup.path(options.year, options.day, DEFAULT_NAME),
+    };
+    let input_dir = input
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(input_dir)?;
+    fs::create_dir_all(&paths.build)?;
+
+    let mut watcher = notify::Watcher::new(&[paths.day.clone(), input_dir.to_path_buf()])?;
+    println!(
+        "Watching {} and {}",
+        paths.day.display(),
+        input_dir.display()
+    );
+    let mut previous = cycle(options, &paths, None);
+    loop {
+        let changed: Vec<PathBuf> = watcher
+            .wait()?
+            .into_iter()
+            .filter(|path| relevant(path))
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        for path in changed.iter() {
+            println!("\nChanged {}", path.display());
+        }
+        if let Some(answers) = cycle(options, &paths, previous.as_ref()) {
+            previous = Some(answers);
+        }
+    }
+}
+
+// Linux tells us about changes through inotify, which std has no wrapper for
+#[cfg(target_os = "linux")]
+mod notify {
+    use std::convert::TryInto;
+    use std::ffi::CString;
+    use std::io;
+    use std::os::raw::{c_char, c_int, c_void};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::PathBuf;
+
+    #[repr(C)]
+    struct PollFd {
+        fd: c_int,
+        events: i16,
+        revents: i16,
+    }
+
+    extern "C" {
+        fn inotify_init1(flags: c_int) -> c_int;
+        fn inotify_add_watch(fd: c_int, path: *const c_char, mask: u32) -> c_int;
+        fn poll(fds: *mut PollFd, count: u64, timeout: c_int) -> c_int;
+        fn read(fd: c_int, buffer: *mut c_void, count: usize) -> isize;
+        fn close(fd: c_int) -> c_int;
+    }
+
+    const IN_CLOEXEC: c_int = 0o2000000;
+    // A file finished being written, moved in the way many editors save, or deleted
+    const IN_CLOSE_WRITE: u32 = 0x8;
+    const IN_MOVED_TO: u32 = 0x80;
+    const IN_DELETE: u32 = 0x200;
+    const POLLIN: i16 = 0x1;
+    // A save often takes a few writes, so changes this close together are gathered into one
+    const SETTLE_MS: c_int = 200;
+    // An event is a watch, mask, cookie and name length followed by the name
+    const HEADER: usize = 16;
+
+    pub struct Watcher {
+        fd: c_int,
+        dirs: Vec<(c_int, PathBuf)>,
+    }
+
+    impl Watcher {
+        pub fn new(dirs: &[PathBuf]) -> io::Result<Watcher> {
+            let fd = unsafe { inotify_init1(IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut watcher = Watcher {
+                fd,
+                dirs: Vec::new(),
+            };
+            for dir in dirs {
+                let path = CString::new(dir.as_os_str().as_bytes())?;
+                let mask = IN_CLOSE_WRITE | IN_MOVED_TO | IN_DELETE;
+                let watch = unsafe { inotify_add_watch(fd, path.as_ptr(), mask) };
+                if watch < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                watcher.dirs.push((watch, dir.clone()));
+            }
+            Ok(watcher)
+        }
+
+        // Whether events arrived within the timeout, which is forever when negative
+        fn ready(&self, timeout: c_int) -> io::Result<bool> {
+            let mut fd = PollFd {
+                fd: self.fd,
+                events: POLLIN,
+                revents: 0,
+            };
+            match unsafe { poll(&mut fd, 1, timeout) } {
+                -1 => Err(io::Error::last_os_error()),
+                ready => Ok(ready > 0),
+            }
+        }
+
+        fn read(&self) -> io::Result<Vec<PathBuf>> {
+            let mut buffer = [0u8; 4096];
+            let length = unsafe { read(self.fd, buffer.as_mut_ptr() as *mut c_void, buffer.len()) };
+            if length < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(events(&buffer[..length as usize])
+                .into_iter()
+                .filter_map(|(watch, name)| {
+                    let (_, dir) = self.dirs.iter().find(|(other, _)| *other == watch)?;
+                    Some(dir.join(name))
+                })
+                .collect())
+        }
+
+        // Blocks until something changes, then gives every path which changed before things settled
+        pub fn wait(&mut self) -> io::Result<Vec<PathBuf>> {
+            self.ready(-1)?;
+            let mut changed = Vec::new();
+            loop {
+                changed.extend(self.read()?);
+                if !self.ready(SETTLE_MS)? {
+                    break;
+                }
+            }
+            changed.sort();
+            changed.dedup();
+            Ok(changed)
+        }
+    }
+
+    impl Drop for Watcher {
+        fn drop(&mut self) {
+            unsafe {
+                close(self.fd);
+            }
+        }
+    }
+
+    // The watch and file name of each event read from inotify, names are padded with nuls
+    pub fn events(buffer: &[u8]) -> Vec<(c_int, String)> {
+        let mut events = Vec::new();
+        let mut offset = 0;
+        while offset + HEADER <= buffer.len() {
+            let word = |at: usize| {
+                u32::from_ne_bytes(buffer[offset + at..offset + at + 4].try_into().unwrap())
+            };
+            let (watch, length) = (word(0) as c_int, word(12) as usize);
+            let name = &buffer[offset + HEADER..(offset + HEADER + length).min(buffer.len())];
+            let name = String::from_utf8_lossy(name);
+            events.push((watch, name.trim_end_matches('\0').to_string()));
+            offset += HEADER + length;
+        }
+        events
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod notify {
+    use std::io;
+    use std::path::PathBuf;
+
+    pub struct Watcher;
+
+    impl Watcher {
+        pub fn new(_: &[PathBuf]) -> io::Result<Watcher> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "aoc watch needs inotify, which only Linux has",
+            ))
+        }
+
+        pub fn wait(&mut self) -> io::Result<Vec<PathBuf>> {
+            unreachable!()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, relevant, Answers, Options};
+    use std::path::Path;
+
+    fn options(args: &str) -> (u16, u8, Vec<String>) {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        let options = Options::parse(&args);
+        (options.year, options.day, options.inputs)
+    }
+
+    #[test]
+    fn test_options() {
+        let none: Vec<String> = Vec::new();
+        assert_eq!(options("--day=5 --year=2023"), (2023, 5, none.clone()));
+        assert_eq!(options("--day 5 --year 2023"), (2023, 5, none.clone()));
+        assert_eq!(options("2023 5"), (2023, 5, none.clone()));
+        assert_eq!(
+            options("5 --name alice --input=in.txt"),
+            (
+                2023,
+                5,
+                vec!["--name=alice".to_string(), "--input=in.txt".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let first: Answers = vec![
+            (1, Ok("142".to_string())),
+            (2, Err("No digits".to_string())),
+        ];
+        assert_eq!(
+            diff(&first, None),
+            vec!["Part 1: 142", "Part 2 failed: No digits"]
+        );
+
+        let second: Answers = vec![(1, Ok("142".to_string())), (2, Ok("281".to_string()))];
+        assert_eq!(
+            diff(&second, Some(&first)),
+            vec!["Part 1: 142 (unchanged)", "Part 2: 281 (was failing)"]
+        );
+        let third: Answers = vec![
+            (1, Ok("141".to_string())),
+            (2, Err("No digits".to_string())),
+        ];
+        assert_eq!(
+            diff(&third, Some(&second)),
+            vec![
+                "Part 1: 141 (was 142)",
+                "Part 2 failed: No digits (was 281)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relevant() {
+        assert!(relevant(Path::new("src/2023/day-8/compact.rs")));
+        assert!(relevant(Path::new("inputs/2023/8/alice.txt")));
+        assert!(!relevant(Path::new("src/2023/day-8/.main.rs.swp")));
+        assert!(!relevant(Path::new("src/2023/day-8/4913")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_events() {
+        let mut buffer = Vec::new();
+        for (watch, name) in [
+            (1i32, "main.rs\0\0\0\0\0\0\0\0\0"),
+            (2, "input.txt\0\0\0\0\0\0\0"),
+        ] {
+            buffer.extend_from_slice(&watch.to_ne_bytes());
+            buffer.extend_from_slice(&0x8u32.to_ne_bytes());
+            buffer.extend_from_slice(&0u32.to_ne_bytes());
+            buffer.extend_from_slice(&(name.len() as u32).to_ne_bytes());
+            buffer.extend_from_slice(name.as_bytes());
+        }
+        assert_eq!(
+            super::notify::events(&buffer),
+            vec![(1, "main.rs".to_string()), (2, "input.txt".to_string())]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_watcher() {
+        use std::env;
+        use std::fs;
+        use std::process;
+        use std::slice;
+        use std::thread;
+        use std::time::Duration;
+
+        let dir = env::temp_dir().join(format!("aoc-watch-test-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut watcher = super::notify::Watcher::new(slice::from_ref(&dir)).unwrap();
+
+        let file = dir.join("main.rs");
+        let writer = {
+            let file = file.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                // Saved twice in quick succession, which is seen as one change
+                fs::write(&file, "fn main() {}").unwrap();
+                fs::write(&file, "fn main() {}\n").unwrap();
+            })
+        };
+        assert_eq!(watcher.wait().unwrap(), vec![file]);
+        writer.join().unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,44 @@
+use days::Day;
+
+// Every day is also its own binary, so the parts the runner does not call are expected to be unused here
+#[allow(dead_code)]
+#[path = "../2023/day-1/main.rs"]
+pub mod day_1;
+#[allow(dead_code)]
+#[path = "../2023/day-2/main.rs"]
+pub mod day_2;
+#[allow(dead_code)]
+#[path = "../2023/day-3/main.rs"]
+pub mod day_3;
+#[allow(dead_code)]
+#[path = "../2023/day-4/main.rs"]
+pub mod day_4;
+#[allow(dead_code)]
+#[path = "../2023/day-5/main.rs"]
+pub mod day_5;
+#[allow(dead_code)]
+#[path = "../2023/day-6/main.rs"]
+pub mod day_6;
+#[allow(dead_code)]
+#[path = "../2023/day-7/main.rs"]
+pub mod day_7;
+#[allow(dead_code)]
+#[path = "../2023/day-8/main.rs"]
+pub mod day_8;
+#[allow(dead_code)]
+#[path = "../2023/day-9/main.rs"]
+pub mod day_9;
+// aoc new adds modules above this line
+
+pub static DAYS: &[Day] = &[
+    day!(2023, 1, day_1, Calibration),
+    day!(2023, 2, day_2, CubeGames),
+    day!(2023, 3, day_3, Schematic),
+    day!(2023, 4, day_4, Scratchcards),
+    day!(2023, 5, day_5, Almanac, min_seed_range_loction),
+    day!(2023, 6, day_6, Races),
+    day!(2023, 7, day_7, Hands),
+    day!(2023, 8, day_8, Network),
+    day!(2023, 9, day_9, Reports),
+    // aoc new adds days above this line
+];
@@ -0,0 +1,186 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Puzzle inputs are kept out of the solutions in inputs/<year>/<day>/<name>.txt, where the name is
+// "input" or whoever the input belongs to, so a team's inputs can sit side by side
+pub const DEFAULT_NAME: &str = "input";
+
+// The checkout this file is in, found from wherever a day or the runner is run
+pub fn repo_root() -> Option<PathBuf> {
+    let dir = env::current_dir().ok()?;
+    dir.ancestors()
+        .find(|dir| dir.join("src").join("inputs.rs").is_file())
+        .map(Path::to_path_buf)
+}
+
+// An empty file is a placeholder waiting for the puzzle input
+pub fn saved(path: &Path) -> bool {
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.len() > 0)
+}
+
+// Where to look for one day's input
+#[derive(Debug, PartialEq)]
+pub struct Lookup {
+    pub root: PathBuf,
+    // One of the named inputs in the store, otherwise the default input
+    pub name: Option<String>,
+    // A file outside the store, which skips it entirely
+    pub file: Option<PathBuf>,
+}
+
+impl Lookup {
+    // AOC_INPUTS moves the store, AOC_USER picks a named input and AOC_INPUT points at any file
+    pub fn from_env() -> Lookup {
+        Lookup {
+            root: env::var_os("AOC_INPUTS")
+                .map(PathBuf::from)
+                .or_else(|| repo_root().map(|root| root.join("inputs")))
+                .unwrap_or_else(|| PathBuf::from("inputs")),
+            name: env::var("AOC_USER").ok(),
+            file: env::var_os("AOC_INPUT").map(PathBuf::from),
+        }
+    }
+
+    // --inputs=DIR, --name=NAME and --input=PATH win over the environment, other arguments are left alone
+    pub fn from_args(args: &[String]) -> Lookup {
+        let mut lookup = Lookup::from_env();
+        for arg in args {
+            match arg.split_once('=') {
+                Some(("--inputs", value)) => lookup.root = PathBuf::from(value),
+                Some(("--name", value)) => lookup.name = Some(value.to_string()),
+                Some(("--input", value)) => lookup.file = Some(PathBuf::from(value)),
+                _ => {}
+            }
+        }
+        lookup
+    }
+
+    pub fn path(&self, year: u16, day: u8, name: &str) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(day.to_string())
+            .join(format!("{}.txt", name))
+    }
+
+    // Every named input saved for the day, sorted
+    pub fn names(&self, year: u16, day: u8) -> Vec<String> {
+        let dir = self.root.join(year.to_string()).join(day.to_string());
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                match path.extension() {
+                    Some(extension) if extension == "txt" && saved(&path) => {
+                        Some(path.file_stem()?.to_string_lossy().into_owned())
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    // Paths to try in order, the default input falls back to the input.txt beside the day's solution
+    fn candidates(&self, year: u16, day: u8) -> Vec<PathBuf> {
+        match (&self.file, &self.name) {
+            (Some(file), _) => vec![file.clone()],
+            (None, Some(name)) => vec![self.path(year, day, name)],
+            (None, None) => vec![
+                self.path(year, day, DEFAULT_NAME),
+                repo_root()
+                    .unwrap_or_default()
+                    .join("src")
+                    .join(year.to_string())
+                    .join(format!("day-{}", day))
+                    .join("input.txt"),
+            ],
+        }
+    }
+
+    pub fn find(&self, year: u16, day: u8) -> Result<PathBuf, InputError> {
+        let tried = self.candidates(year, day);
+        match tried.iter().find(|path| saved(path)) {
+            Some(path) => Ok(path.clone()),
+            None => Err(InputError::Missing {
+                year,
+                day,
+                tried,
+                available: self.names(year, day),
+            }),
+        }
+    }
+
+    pub fn read(&self, year: u16, day: u8) -> Result<String, InputError> {
+        self.open(year, day).map(|(_, input)| input)
+    }
+
+    // The input along with the path it was read from
+    pub fn open(&self, year: u16, day: u8) -> Result<(PathBuf, String), InputError> {
+        let path = self.find(year, day)?;
+        match fs::read_to_string(&path) {
+            Ok(input) => Ok((path, input)),
+            Err(err) => Err(InputError::Unreadable {
+                path,
+                error: err.to_string(),
+            }),
+        }
+    }
+}
+
+// The day's input from the command line, environment or store
+pub fn load(year: u16, day: u8) -> Result<String, InputError> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    Lookup::from_args(&args).read(year, day)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputError {
+    Missing {
+        year: u16,
+        day: u8,
+        tried: Vec<PathBuf>,
+        // Other people's inputs for the same day, which --name can pick
+        available: Vec<String>,
+    },
+    Unreadable {
+        path: PathBuf,
+        error: String,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing {
+                year,
+                day,
+                tried,
+                available,
+            } => {
+                let tried: Vec<String> = tried
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                write!(
+                    f,
+                    "Input missing for {} day {}, looked for {}",
+                    year,
+                    day,
+                    tried.join(", ")
+                )?;
+                if !available.is_empty() {
+                    write!(f, " (saved inputs: {})", available.join(", "))?;
+                }
+                Ok(())
+            }
+            InputError::Unreadable { path, error } => {
+                write!(f, "Can't read input {}: {}", path.display(), error)
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+// Shared by the days with work worth splitting across cores. Built with --cfg 'feature="parallel"'
+// the work runs on one thread per core, otherwise in order on the calling thread. Either way the
+// results come back in the order of the items, so both give the same answers
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+// Runs the job over every item on up to `jobs` threads, which take the next item as they finish one.
+// The runner's pool uses this whatever the feature, the days only through map
+#[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+pub fn map_with<T, R, F>(items: &[T], jobs: usize, job: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.clamp(1, items.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut index = next.fetch_add(1, Ordering::Relaxed);
+                    while let Some(item) = items.get(index) {
+                        let result = job(item);
+                        results.lock().unwrap()[index] = Some(result);
+                        index = next.fetch_add(1, Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+        // A panic carries on in the calling thread with its own message
+        for worker in workers {
+            if let Err(payload) = worker.join() {
+                panic::resume_unwind(payload);
+            }
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("Every item is taken by a worker"))
+        .collect()
+}
+
+#[cfg(feature = "parallel")]
+pub fn map<T, R, F>(items: &[T], job: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    map_with(items, threads, job)
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn map<T, R, F>(items: &[T], job: F) -> Vec<R>
+where
+    F: Fn(&T) -> R,
+{
+    items.iter().map(job).collect()
+}
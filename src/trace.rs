@@ -0,0 +1,123 @@
+// Shared by every day for a look at intermediate state without editing code. Pass --verbose for
+// spans around each parser and solver with what they found, or --trace for every step as well, or
+// set AOC_TRACE=verbose or AOC_TRACE=trace. It all goes to stderr, so stdout is left to the answers
+use std::cell::Cell;
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Level {
+    Off = 0,
+    Verbose = 1,
+    Trace = 2,
+}
+
+// Read from the arguments and environment the first time it's needed
+const UNSET: u8 = u8::MAX;
+static LEVEL: AtomicU8 = AtomicU8::new(UNSET);
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+// The command line wins over AOC_TRACE
+pub fn parse_level(args: &[String], var: Option<&str>) -> Level {
+    if args.iter().any(|arg| arg == "--trace") {
+        Level::Trace
+    } else if args.iter().any(|arg| arg == "--verbose") {
+        Level::Verbose
+    } else {
+        match var {
+            Some("trace") => Level::Trace,
+            Some("verbose") => Level::Verbose,
+            _ => Level::Off,
+        }
+    }
+}
+
+pub fn level() -> Level {
+    let level = match LEVEL.load(Ordering::Relaxed) {
+        UNSET => {
+            let args: Vec<String> = env::args().collect();
+            let level = parse_level(&args, env::var("AOC_TRACE").ok().as_deref());
+            LEVEL.store(level as u8, Ordering::Relaxed);
+            level as u8
+        }
+        level => level,
+    };
+    match level {
+        2 => Level::Trace,
+        1 => Level::Verbose,
+        _ => Level::Off,
+    }
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+// Indented under the spans it happened in
+pub fn event(message: fmt::Arguments) {
+    DEPTH.with(|depth| eprintln!("{:width$}{}", "", message, width = depth.get() * 2));
+}
+
+// Opened with a line and closed with another saying how long it took, indenting everything between
+pub struct Span {
+    open: Option<(String, Instant)>,
+}
+
+impl Span {
+    pub fn enter<F: FnOnce() -> String>(level: Level, name: F) -> Span {
+        if !enabled(level) {
+            return Span { open: None };
+        }
+        let name = name();
+        event(format_args!("> {}", name));
+        DEPTH.with(|depth| depth.set(depth.get() + 1));
+        Span {
+            open: Some((name, Instant::now())),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some((name, start)) = self.open.take() {
+            DEPTH.with(|depth| depth.set(depth.get() - 1));
+            event(format_args!("< {} {:?}", name, start.elapsed()));
+        }
+    }
+}
+
+// Macros name the module by its path from where they're used, so a module below a day needs
+// `use super::trace;` to use them. Not every day uses every macro
+
+// Lasts until the value it returns is dropped, so keep it as `let _span = span!(...)`
+#[allow(unused_macros)]
+macro_rules! span {
+    ($($arg:tt)*) => {
+        trace::Span::enter(trace::Level::Verbose, || format!($($arg)*))
+    };
+}
+
+// What a parser or solver found, such as how many of something there are
+#[allow(unused_macros)]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if trace::enabled(trace::Level::Verbose) {
+            trace::event(format_args!($($arg)*));
+        }
+    };
+}
+
+// A single step, which may be printed millions of times
+#[allow(unused_macros)]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if trace::enabled(trace::Level::Trace) {
+            trace::event(format_args!($($arg)*));
+        }
+    };
+}